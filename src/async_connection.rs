@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+use futures_core::Stream;
+use tokio::sync::{mpsc, oneshot};
+use crate::errors::{InformixError, Result};
+use crate::transaction;
+use crate::{Connection, Statement, Value};
+
+// Rows buffered ahead of a slow `RowStream` consumer before the worker waits
+const ROW_BUFFER: usize = 64;
+
+type Reply<T> = oneshot::Sender<Result<T>>;
+type Response<T> = oneshot::Receiver<Result<T>>;
+type RowSender = mpsc::Sender<Result<Vec<Value>>>;
+
+enum Target {
+    Sql(String),
+    Prepared(u64),
+}
+
+enum Command {
+    Execute { target: Target, params: Vec<Value>, reply: Reply<()> },
+    Query { target: Target, params: Vec<Value>, rows: RowSender },
+    Prepare { sql: String, reply: Reply<u64> },
+    Close { id: u64 },
+    Begin { reply: Reply<()> },
+    Commit { reply: Reply<()> },
+    /// Sent without a reply when an `AsyncTransaction` is dropped.
+    Rollback { reply: Option<Reply<()>> },
+}
+
+/// A connection driven from async code.
+///
+/// The blocking `Connection` lives on a dedicated worker thread; each method
+/// sends it a command and awaits the result over a channel, so no CLI call
+/// runs on the async executor. Commands run one at a time in the order sent.
+/// Dropping the `AsyncConnection` and everything borrowed from it stops the
+/// worker and closes the connection.
+pub struct AsyncConnection {
+    commands: std_mpsc::Sender<Command>,
+    // Set while a `RowStream` is unfinished; the worker is stuck behind it
+    // once its buffer fills, so awaiting another command would never return
+    streaming: Arc<AtomicBool>,
+}
+
+impl AsyncConnection {
+    /// Opens a connection on a new worker thread.
+    pub async fn connect(conn_string: &str) -> Result<Self> {
+        let conn_string = conn_string.to_string();
+        let (reply, connected) = oneshot::channel();
+        let (commands, receiver) = std_mpsc::channel();
+        thread::Builder::new()
+            .name("informix-connection".to_string())
+            .spawn(move || {
+                let conn = Connection::new().and_then(|conn| {
+                    conn.connect_with_string(&conn_string)?;
+                    Ok(conn)
+                });
+                match conn {
+                    Ok(conn) => {
+                        let _ = reply.send(Ok(()));
+                        run(conn, receiver);
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(e));
+                    }
+                }
+            })
+            .map_err(|e| InformixError::ConnectionError(format!("Failed to start worker thread: {}", e), Vec::new()))?;
+        connected.await.map_err(|_| worker_stopped())??;
+        Ok(AsyncConnection { commands, streaming: Arc::default() })
+    }
+
+    /// Moves an already open connection onto a new worker thread.
+    pub fn from_connection(conn: Connection) -> Result<Self> {
+        let (commands, receiver) = std_mpsc::channel();
+        thread::Builder::new()
+            .name("informix-connection".to_string())
+            .spawn(move || run(conn, receiver))
+            .map_err(|e| InformixError::ConnectionError(format!("Failed to start worker thread: {}", e), Vec::new()))?;
+        Ok(AsyncConnection { commands, streaming: Arc::default() })
+    }
+
+    /// Executes a statement, binding `params` to its `?` placeholders.
+    pub async fn execute(&self, sql: &str, params: &[Value]) -> Result<()> {
+        self.request(|reply| Command::Execute { target: Target::Sql(sql.to_string()), params: params.to_vec(), reply })
+            .await
+    }
+
+    /// Runs a query and collects every row.
+    pub async fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Vec<Value>>> {
+        collect(self.query_stream(sql, params)?).await
+    }
+
+    /// Runs a query and returns its rows as a `Stream`.
+    ///
+    /// Rows are fetched ahead of the consumer into a small buffer; the worker
+    /// waits when it is full, and stops fetching when the stream is dropped.
+    /// Until the stream is finished or dropped, other commands on this
+    /// connection fail with `InformixError::ConnectionBusy`.
+    pub fn query_stream(&self, sql: &str, params: &[Value]) -> Result<RowStream> {
+        self.stream(Target::Sql(sql.to_string()), params)
+    }
+
+    /// Prepares a statement on the worker for repeated execution.
+    pub async fn prepare(&self, sql: &str) -> Result<AsyncStatement<'_>> {
+        let id = self.request(|reply| Command::Prepare { sql: sql.to_string(), reply }).await?;
+        Ok(AsyncStatement { conn: self, id })
+    }
+
+    /// Starts a transaction; see `Connection::transaction`.
+    ///
+    /// Dropping the returned guard without calling `commit` rolls the
+    /// transaction back, as does dropping this future before it completes.
+    pub async fn transaction(&mut self) -> Result<AsyncTransaction<'_>> {
+        let response = self.send_request(|reply| Command::Begin { reply })?;
+        // Built before the reply arrives, so that a cancelled call still queues a rollback
+        let txn = AsyncTransaction { conn: self, finished: false };
+        receive(response).await?;
+        Ok(txn)
+    }
+
+    fn send(&self, command: Command) -> Result<()> {
+        self.commands.send(command).map_err(|_| worker_stopped())
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(Reply<T>) -> Command) -> Result<T> {
+        receive(self.send_request(command)?).await
+    }
+
+    /// Sends a command without waiting for its reply.
+    fn send_request<T>(&self, command: impl FnOnce(Reply<T>) -> Command) -> Result<Response<T>> {
+        if self.streaming.load(Ordering::Acquire) {
+            return Err(InformixError::ConnectionBusy);
+        }
+        let (reply, response) = oneshot::channel();
+        self.send(command(reply))?;
+        Ok(response)
+    }
+
+    fn stream(&self, target: Target, params: &[Value]) -> Result<RowStream> {
+        if self.streaming.swap(true, Ordering::AcqRel) {
+            return Err(InformixError::ConnectionBusy);
+        }
+        // Built first so that a failed send clears the flag again
+        let mut stream = RowStream { rows: None, streaming: Arc::clone(&self.streaming) };
+        let (rows, receiver) = mpsc::channel(ROW_BUFFER);
+        self.send(Command::Query { target, params: params.to_vec(), rows })?;
+        stream.rows = Some(receiver);
+        Ok(stream)
+    }
+}
+
+/// A statement prepared on an `AsyncConnection`'s worker.
+///
+/// It is freed on the worker when dropped.
+pub struct AsyncStatement<'conn> {
+    conn: &'conn AsyncConnection,
+    id: u64,
+}
+
+impl AsyncStatement<'_> {
+    pub async fn execute(&self, params: &[Value]) -> Result<()> {
+        self.conn.request(|reply| Command::Execute { target: Target::Prepared(self.id), params: params.to_vec(), reply })
+            .await
+    }
+
+    pub async fn query(&self, params: &[Value]) -> Result<Vec<Vec<Value>>> {
+        collect(self.query_stream(params)?).await
+    }
+
+    pub fn query_stream(&self, params: &[Value]) -> Result<RowStream> {
+        self.conn.stream(Target::Prepared(self.id), params)
+    }
+}
+
+impl Drop for AsyncStatement<'_> {
+    fn drop(&mut self) {
+        let _ = self.conn.send(Command::Close { id: self.id });
+    }
+}
+
+/// A transaction on an `AsyncConnection`, started with `AsyncConnection::transaction`.
+///
+/// Dropping the guard without calling `commit` rolls the transaction back.
+pub struct AsyncTransaction<'a> {
+    conn: &'a AsyncConnection,
+    finished: bool,
+}
+
+impl AsyncTransaction<'_> {
+    /// Commits the transaction.
+    ///
+    /// If the command cannot be sent, e.g. because a `RowStream` is still
+    /// open, the guard is dropped and the transaction rolled back.
+    pub async fn commit(mut self) -> Result<()> {
+        let response = self.conn.send_request(|reply| Command::Commit { reply })?;
+        self.finished = true;
+        receive(response).await
+    }
+
+    pub async fn rollback(mut self) -> Result<()> {
+        let response = self.conn.send_request(|reply| Command::Rollback { reply: Some(reply) })?;
+        self.finished = true;
+        receive(response).await
+    }
+}
+
+impl Deref for AsyncTransaction<'_> {
+    type Target = AsyncConnection;
+
+    fn deref(&self) -> &AsyncConnection {
+        self.conn
+    }
+}
+
+impl Drop for AsyncTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // The rollback is queued behind earlier commands; nobody waits for it
+            let _ = self.conn.send(Command::Rollback { reply: None });
+        }
+    }
+}
+
+/// Rows of a query, produced by the connection's worker thread.
+pub struct RowStream {
+    // `None` once every row has been received
+    rows: Option<mpsc::Receiver<Result<Vec<Value>>>>,
+    streaming: Arc<AtomicBool>,
+}
+
+impl RowStream {
+    fn finish(&mut self) {
+        if self.rows.take().is_some() {
+            self.streaming.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl Stream for RowStream {
+    type Item = Result<Vec<Value>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(rows) = self.rows.as_mut() else {
+            return Poll::Ready(None);
+        };
+        let row = rows.poll_recv(cx);
+        if let Poll::Ready(None) = row {
+            self.finish();
+        }
+        row
+    }
+}
+
+impl Drop for RowStream {
+    fn drop(&mut self) {
+        // The worker stops fetching at its next send, ahead of any later command
+        self.finish();
+    }
+}
+
+async fn collect(mut stream: RowStream) -> Result<Vec<Vec<Value>>> {
+    let mut rows = Vec::new();
+    while let Some(row) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+        rows.push(row?);
+    }
+    Ok(rows)
+}
+
+async fn receive<T>(response: Response<T>) -> Result<T> {
+    response.await.map_err(|_| worker_stopped())?
+}
+
+fn worker_stopped() -> InformixError {
+    InformixError::ConnectionError("Connection worker has stopped".to_string(), Vec::new())
+}
+
+/// The worker loop: runs commands until every sender is dropped.
+fn run(conn: Connection, commands: std_mpsc::Receiver<Command>) {
+    let mut statements: HashMap<u64, Statement<'_>> = HashMap::new();
+    let mut next_id = 0;
+    // Whether autocommit must be restored, while a transaction is open
+    let mut open_transaction: Option<bool> = None;
+
+    for command in commands {
+        match command {
+            Command::Execute { target, params, reply } => {
+                let result = with_statement(&conn, &statements, target, |stmt| bind_and_execute(stmt, &params));
+                let _ = reply.send(result);
+            }
+            Command::Query { target, params, rows } => {
+                let result = with_statement(&conn, &statements, target, |stmt| {
+                    bind_and_execute(stmt, &params)?;
+                    send_rows(stmt, &rows)
+                });
+                if let Err(e) = result {
+                    let _ = rows.blocking_send(Err(e));
+                }
+            }
+            Command::Prepare { sql, reply } => {
+                let result = conn.prepare(&sql).map(|stmt| {
+                    next_id += 1;
+                    statements.insert(next_id, stmt);
+                    next_id
+                });
+                let _ = reply.send(result);
+            }
+            Command::Close { id } => {
+                statements.remove(&id);
+            }
+            Command::Begin { reply } => {
+                let result = if open_transaction.is_some() {
+                    Err(InformixError::TransactionError("A transaction is already open".to_string(), Vec::new()))
+                } else {
+                    transaction::begin(&conn).map(|restore| open_transaction = Some(restore))
+                };
+                let _ = reply.send(result);
+            }
+            Command::Commit { reply } => {
+                let _ = reply.send(end_transaction(&conn, open_transaction.take(), Connection::commit));
+            }
+            Command::Rollback { reply } => {
+                let result = end_transaction(&conn, open_transaction.take(), Connection::rollback);
+                if let Some(reply) = reply {
+                    let _ = reply.send(result);
+                }
+            }
+        }
+    }
+    if let Some(restore) = open_transaction {
+        let _ = transaction::finish(&conn, restore, Connection::rollback);
+    }
+}
+
+/// Runs `f` on a prepared statement, or on a fresh one prepared from `sql`.
+fn with_statement<T>(
+    conn: &Connection,
+    statements: &HashMap<u64, Statement<'_>>,
+    target: Target,
+    f: impl FnOnce(&Statement<'_>) -> Result<T>,
+) -> Result<T> {
+    match target {
+        Target::Sql(sql) => f(&conn.prepare(&sql)?),
+        Target::Prepared(id) => {
+            let stmt = statements.get(&id)
+                .ok_or(InformixError::StatementNotFound(id))?;
+            // A previous query may have been dropped before reading every row
+            stmt.close_cursor()?;
+            f(stmt)
+        }
+    }
+}
+
+fn bind_and_execute(stmt: &Statement<'_>, params: &[Value]) -> Result<()> {
+    for (i, param) in params.iter().enumerate() {
+        stmt.bind_parameter((i + 1) as u16, param)?;
+    }
+    stmt.execute()
+}
+
+/// Sends rows until the result set ends or the receiver is dropped.
+fn send_rows(stmt: &Statement<'_>, rows: &RowSender) -> Result<()> {
+    while let Some(row) = stmt.fetch_values()? {
+        if rows.blocking_send(Ok(row)).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn end_transaction(conn: &Connection, open_transaction: Option<bool>, end: fn(&Connection) -> Result<()>) -> Result<()> {
+    match open_transaction {
+        Some(restore) => transaction::finish(conn, restore, end),
+        None => Err(InformixError::TransactionError("No transaction is open".to_string(), Vec::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::task::{Wake, Waker};
+    use std::thread::JoinHandle;
+
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    /// A connection whose worker acknowledges every command and returns their names once stopped.
+    fn connection() -> (AsyncConnection, JoinHandle<Vec<&'static str>>) {
+        let (commands, receiver) = std_mpsc::channel();
+        let worker = thread::spawn(move || {
+            receiver.into_iter()
+                .map(|command| match command {
+                    Command::Execute { reply, .. } => { let _ = reply.send(Ok(())); "execute" }
+                    Command::Query { .. } => "query",
+                    Command::Prepare { reply, .. } => { let _ = reply.send(Ok(1)); "prepare" }
+                    Command::Close { .. } => "close",
+                    Command::Begin { reply } => { let _ = reply.send(Ok(())); "begin" }
+                    Command::Commit { reply } => { let _ = reply.send(Ok(())); "commit" }
+                    Command::Rollback { reply } => {
+                        if let Some(reply) = reply {
+                            let _ = reply.send(Ok(()));
+                        }
+                        "rollback"
+                    }
+                })
+                .collect()
+        });
+        (AsyncConnection { commands, streaming: Arc::default() }, worker)
+    }
+
+    #[test]
+    fn commit_with_an_open_stream_rolls_back() {
+        let (mut conn, worker) = connection();
+        block_on(async {
+            let txn = conn.transaction().await.unwrap();
+            let stream = txn.query_stream("SELECT 1 FROM systables", &[]).unwrap();
+            assert!(matches!(txn.commit().await, Err(InformixError::ConnectionBusy)));
+            drop(stream);
+            conn.execute("DELETE FROM t", &[]).await.unwrap();
+        });
+        drop(conn);
+        assert_eq!(worker.join().unwrap(), ["begin", "query", "rollback", "execute"]);
+    }
+
+    #[test]
+    fn cancelled_begin_rolls_back() {
+        let (mut conn, worker) = connection();
+        {
+            let mut begin = std::pin::pin!(conn.transaction());
+            let waker = Waker::from(Arc::new(Unpark(thread::current())));
+            // Sends the command, then the future is dropped whether or not the reply has arrived
+            let _ = begin.as_mut().poll(&mut Context::from_waker(&waker));
+        }
+        drop(conn);
+        assert_eq!(worker.join().unwrap(), ["begin", "rollback"]);
+    }
+
+    #[test]
+    fn commands_fail_while_a_stream_is_open() {
+        let (conn, worker) = connection();
+        block_on(async {
+            let stream = conn.query_stream("SELECT 1 FROM systables", &[]).unwrap();
+            assert!(matches!(conn.execute("DELETE FROM t", &[]).await, Err(InformixError::ConnectionBusy)));
+            assert!(matches!(conn.query_stream("SELECT 2 FROM systables", &[]), Err(InformixError::ConnectionBusy)));
+            drop(stream);
+            conn.execute("DELETE FROM t", &[]).await.unwrap();
+        });
+        drop(conn);
+        assert_eq!(worker.join().unwrap(), ["query", "execute"]);
+    }
+}
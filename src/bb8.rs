@@ -0,0 +1,57 @@
+use crate::blocking;
+use crate::errors::InformixError;
+use crate::pool::InformixManager;
+
+pub use crate::blocking::ManagedConnection;
+
+/// A bb8 `ManageConnection` for Informix, built from a connection string.
+///
+/// Connections are opened and checked on tokio's blocking thread pool;
+/// `is_valid` resets the session state and runs a health query. Queries run
+/// off the executor through `ManagedConnection::interact`.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use informix_rust::bb8::InformixConnectionManager;
+///
+/// let manager = InformixConnectionManager::new("DSN=stores;UID=informix;PWD=secret");
+/// let pool = bb8::Pool::builder().max_size(10).build(manager).await?;
+/// let conn = pool.get().await?;
+/// conn.interact(|c| c.execute("UPDATE stock SET unit_price = unit_price * 1.05").map(drop)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InformixConnectionManager {
+    inner: InformixManager,
+}
+
+impl InformixConnectionManager {
+    pub fn new(conn_string: &str) -> Self {
+        InformixConnectionManager { inner: InformixManager::new(conn_string) }
+    }
+
+    /// Replaces the `SELECT 1 FROM sysmaster:sysdual` ping run by `is_valid`.
+    pub fn health_query(self, query: &str) -> Self {
+        InformixConnectionManager { inner: self.inner.health_query(query) }
+    }
+}
+
+impl ::bb8::ManageConnection for InformixConnectionManager {
+    type Connection = ManagedConnection;
+    type Error = InformixError;
+
+    async fn connect(&self) -> Result<ManagedConnection, InformixError> {
+        blocking::connect(self.inner.clone()).await
+    }
+
+    async fn is_valid(&self, conn: &mut ManagedConnection) -> Result<(), InformixError> {
+        blocking::recycle(self.inner.clone(), conn).await
+    }
+
+    /// Uses `SQL_ATTR_CONNECTION_DEAD` as read after the connection was last
+    /// used, so it neither blocks nor calls the CLI on the executor.
+    fn has_broken(&self, conn: &mut ManagedConnection) -> bool {
+        conn.is_dead()
+    }
+}
@@ -0,0 +1,88 @@
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use crate::errors::{InformixError, Result};
+use crate::pool::{ConnectionManager, InformixManager};
+use crate::Connection;
+
+/// Runs blocking CLI work on tokio's blocking thread pool, so it never
+/// stalls the async executor. A panic in `f` is resumed in the caller.
+pub(crate) async fn run<F, T>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) => match e.try_into_panic() {
+            Ok(payload) => panic::resume_unwind(payload),
+            Err(e) => Err(InformixError::ConnectionError(format!("Blocking task failed: {}", e), Vec::new())),
+        },
+    }
+}
+
+/// A connection held by an async pool.
+///
+/// The connection is only reachable through `interact`, which runs a closure
+/// on tokio's blocking thread pool. Nothing can keep using the connection
+/// after it goes back to the pool.
+pub struct ManagedConnection {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    conn: Mutex<Connection>,
+    // `SQL_ATTR_CONNECTION_DEAD` as of the last `interact`, so checking it
+    // needs neither the lock nor a CLI call on the executor
+    dead: AtomicBool,
+}
+
+impl ManagedConnection {
+    fn new(conn: Connection) -> Self {
+        ManagedConnection { inner: Arc::new(Inner { conn: Mutex::new(conn), dead: AtomicBool::new(false) }) }
+    }
+
+    /// Runs `f` with the connection on tokio's blocking thread pool.
+    ///
+    /// If the returned future is dropped early, `f` still runs to completion,
+    /// and the pool does not recycle the connection until it has finished.
+    pub async fn interact<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Connection) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        run(move || {
+            let mut conn = lock(&inner.conn);
+            let result = f(&mut conn);
+            inner.dead.store(conn.is_dead().unwrap_or(true), Ordering::Relaxed);
+            result
+        })
+        .await
+    }
+
+    /// Whether the connection was found dead after the last `interact`.
+    #[cfg(feature = "bb8")]
+    pub(crate) fn is_dead(&self) -> bool {
+        self.inner.dead.load(Ordering::Relaxed)
+    }
+}
+
+// A panic inside `interact` leaves the connection usable; recycling resets it
+fn lock(conn: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    conn.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Opens a connection for an async pool.
+pub(crate) async fn connect(manager: InformixManager) -> Result<ManagedConnection> {
+    run(move || manager.connect().map(ManagedConnection::new)).await
+}
+
+/// Resets the session state, then runs the health query.
+pub(crate) async fn recycle(manager: InformixManager, conn: &ManagedConnection) -> Result<()> {
+    conn.interact(move |conn| {
+        manager.reset(conn)?;
+        manager.is_valid(conn)
+    })
+    .await
+}
@@ -0,0 +1,60 @@
+use std::os::raw::c_void;
+use std::sync::{Arc, Mutex, PoisonError};
+use crate::errors::{InformixError, Result};
+use crate::{get_diagnostics, SQLCancel};
+use crate::{SQL_HANDLE_STMT, SQL_SUCCESS, SQL_SUCCESS_WITH_INFO};
+
+/// The statement handle as seen by its `CancelHandle`s; null once freed.
+pub(crate) struct CancelTarget {
+    handle: Mutex<*mut c_void>,
+}
+
+// SAFETY: the pointer is only passed to `SQLCancel`, which the CLI allows from
+// any thread, and the mutex keeps it from being freed during that call.
+unsafe impl Send for CancelTarget {}
+unsafe impl Sync for CancelTarget {}
+
+impl CancelTarget {
+    pub(crate) fn new(handle: *mut c_void) -> Self {
+        CancelTarget { handle: Mutex::new(handle) }
+    }
+
+    /// Frees the statement handle with `free`, after which cancelling does nothing.
+    pub(crate) fn release(&self, free: impl FnOnce(*mut c_void)) {
+        let mut handle = self.handle.lock().unwrap_or_else(PoisonError::into_inner);
+        free(*handle);
+        *handle = std::ptr::null_mut();
+    }
+}
+
+/// Cancels a running statement from another thread, via `SQLCancel`.
+///
+/// Obtained from `Statement::cancel_handle`. The interrupted call on the
+/// statement fails with an error of kind `InformixErrorKind::Cancelled`.
+#[derive(Clone)]
+pub struct CancelHandle {
+    target: Arc<CancelTarget>,
+}
+
+impl CancelHandle {
+    pub(crate) fn new(target: Arc<CancelTarget>) -> Self {
+        CancelHandle { target }
+    }
+
+    /// Requests cancellation of the statement's current call. Does nothing
+    /// if the statement is idle or has been dropped.
+    pub fn cancel(&self) -> Result<()> {
+        let handle = self.target.handle.lock().unwrap_or_else(PoisonError::into_inner);
+        if handle.is_null() {
+            return Ok(());
+        }
+        let result = unsafe { SQLCancel(*handle) };
+        if result == SQL_SUCCESS || result == SQL_SUCCESS_WITH_INFO {
+            Ok(())
+        } else {
+            Err(InformixError::SQLExecutionError(
+                "Failed to cancel statement".to_string(), get_diagnostics(SQL_HANDLE_STMT, *handle)
+            ))
+        }
+    }
+}
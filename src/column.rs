@@ -0,0 +1,16 @@
+use std::os::raw::c_short;
+
+/// Description of a result set column, as reported by `SQLDescribeCol` and `SQLColAttribute`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDesc {
+    pub name: String,
+    /// ODBC SQL type code, e.g. `SQL_INTEGER` or `SQL_VARCHAR`.
+    pub sql_type: c_short,
+    /// Informix type name, e.g. `serial` or `money`.
+    pub type_name: String,
+    pub size: u64,
+    pub precision: i16,
+    pub scale: i16,
+    /// `None` when the driver cannot tell whether the column accepts NULLs.
+    pub nullable: Option<bool>,
+}
@@ -0,0 +1,57 @@
+use ::deadpool::managed::{self, Metrics, RecycleError, RecycleResult};
+use crate::blocking;
+use crate::errors::InformixError;
+use crate::pool::InformixManager;
+
+pub use crate::blocking::ManagedConnection;
+
+/// A deadpool pool of Informix connections.
+pub type Pool = managed::Pool<Manager>;
+
+/// A connection checked out of a deadpool `Pool`.
+pub type Object = managed::Object<Manager>;
+
+/// A deadpool `Manager` for Informix, built from a connection string.
+///
+/// Connections are opened and recycled on tokio's blocking thread pool.
+/// Recycling resets the session state and runs a health query. Queries run
+/// off the executor through `ManagedConnection::interact`.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use informix_rust::deadpool::{Manager, Pool};
+///
+/// let pool = Pool::builder(Manager::new("DSN=stores;UID=informix;PWD=secret")).max_size(10).build()?;
+/// let conn = pool.get().await?;
+/// conn.interact(|c| c.execute("UPDATE stock SET unit_price = unit_price * 1.05").map(drop)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Manager {
+    inner: InformixManager,
+}
+
+impl Manager {
+    pub fn new(conn_string: &str) -> Self {
+        Manager { inner: InformixManager::new(conn_string) }
+    }
+
+    /// Replaces the `SELECT 1 FROM sysmaster:sysdual` ping run when recycling.
+    pub fn health_query(self, query: &str) -> Self {
+        Manager { inner: self.inner.health_query(query) }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = ManagedConnection;
+    type Error = InformixError;
+
+    async fn create(&self) -> Result<ManagedConnection, InformixError> {
+        blocking::connect(self.inner.clone()).await
+    }
+
+    async fn recycle(&self, conn: &mut ManagedConnection, _: &Metrics) -> RecycleResult<InformixError> {
+        blocking::recycle(self.inner.clone(), conn).await.map_err(RecycleError::Backend)
+    }
+}
@@ -0,0 +1,56 @@
+use std::os::raw::c_void;
+use std::sync::Arc;
+use crate::errors::{InformixError, Result};
+use crate::{get_diagnostics, SQLAllocHandle, SQLFreeHandle, SQLSetEnvAttr};
+use crate::{SQL_ATTR_ODBC_VERSION, SQL_HANDLE_ENV, SQL_OV_ODBC3, SQL_SUCCESS, SQL_SUCCESS_WITH_INFO};
+
+/// The CLI environment handle, parent of every connection handle.
+///
+/// Share one environment between connections with `Connection::with_environment`;
+/// each connection keeps it alive, so the ENV handle is freed only after the
+/// last connection's DBC handle.
+pub struct Environment {
+    pub(crate) handle: *mut c_void,
+}
+
+impl Environment {
+    /// Allocates an environment handle and selects ODBC 3 behavior.
+    pub fn new() -> Result<Self> {
+        let mut handle: *mut c_void = std::ptr::null_mut();
+        let result = unsafe {
+            SQLAllocHandle(SQL_HANDLE_ENV.into(), std::ptr::null_mut(), &mut handle)
+        };
+        if result != 0 {
+            return Err(InformixError::HandleAllocationError(result));
+        }
+        let env = Environment { handle };
+
+        let result = unsafe {
+            SQLSetEnvAttr(env.handle, SQL_ATTR_ODBC_VERSION, SQL_OV_ODBC3 as *mut c_void, 0)
+        };
+        if result != SQL_SUCCESS && result != SQL_SUCCESS_WITH_INFO {
+            return Err(InformixError::ConnectionError(
+                "Failed to set ODBC version".to_string(), get_diagnostics(SQL_HANDLE_ENV, env.handle)
+            ));
+        }
+        Ok(env)
+    }
+
+    /// Allocates a new environment, ready to be shared between connections.
+    pub fn new_shared() -> Result<Arc<Self>> {
+        Environment::new().map(Arc::new)
+    }
+}
+
+// SAFETY: the CLI serializes access to the ENV handle internally, and after
+// construction it is only used to allocate connections and to be freed.
+unsafe impl Send for Environment {}
+unsafe impl Sync for Environment {}
+
+impl Drop for Environment {
+    fn drop(&mut self) {
+        unsafe {
+            SQLFreeHandle(SQL_HANDLE_ENV, self.handle);
+        }
+    }
+}
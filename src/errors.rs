@@ -1,29 +1,350 @@
 use std::fmt;
 use std::error::Error;
+use std::time::Duration;
 
+/// One diagnostic record returned by `SQLGetDiagRec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub sqlstate: String,
+    /// Informix error code, e.g. -239 for a unique constraint violation.
+    pub native_code: i32,
+    pub message: String,
+    /// Secondary ISAM error code, e.g. -107 when -244 was caused by a locked record.
+    pub isam_code: Option<i32>,
+    pub isam_message: Option<String>,
+}
+
+impl Diagnostic {
+    /// Builds a diagnostic, resolving the ISAM message from the driver's text
+    /// or, failing that, from the well-known ISAM codes.
+    pub(crate) fn new(sqlstate: String, native_code: i32, message: String, isam_code: Option<i32>) -> Self {
+        let isam_message = isam_code.and_then(|code| {
+            message.split_once("ISAM error:")
+                .map(|(_, text)| text.trim().trim_end_matches('.').to_string())
+                .filter(|text| !text.is_empty())
+                .or_else(|| isam_message(code).map(str::to_string))
+        });
+        Diagnostic { sqlstate, native_code, message, isam_code, isam_message }
+    }
+}
+
+fn isam_message(code: i32) -> Option<&'static str> {
+    match code {
+        -100 => Some("duplicate value for a record with unique key"),
+        -103 => Some("illegal key descriptor (too many parts or too long)"),
+        -104 => Some("too many files open"),
+        -105 => Some("illegal key descriptor"),
+        -107 => Some("record is locked"),
+        -111 => Some("no record found"),
+        -112 => Some("no current record"),
+        -113 => Some("the file is locked"),
+        -131 => Some("no free disk space"),
+        -134 => Some("no more locks"),
+        -143 => Some("deadlock detected"),
+        -144 => Some("key value locked"),
+        -154 => Some("lock timeout expired"),
+        -172 => Some("unexpected internal error"),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SQLSTATE = {}, Native Error = {}, Message = {}", self.sqlstate, self.native_code, self.message)?;
+        match (self.isam_code, &self.isam_message) {
+            (Some(code), Some(message)) => write!(f, ", ISAM Error = {} ({})", code, message),
+            (Some(code), None) => write!(f, ", ISAM Error = {}", code),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Classification of common Informix errors, derived from the native error
+/// code and falling back to the SQLSTATE class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InformixErrorKind {
+    UniqueViolation,
+    ForeignKeyViolation,
+    NotNullViolation,
+    CheckViolation,
+    LockTimeout,
+    Deadlock,
+    TableNotFound,
+    ColumnNotFound,
+    SyntaxError,
+    ConnectionFailed,
+    ConnectionLost,
+    PermissionDenied,
+    /// The statement was interrupted with `CancelHandle::cancel`.
+    Cancelled,
+    /// A query, login or connection timeout expired, or `INFORMIXCONTIME` ran
+    /// out; see `Statement::set_query_timeout` and `ConnectOptions`.
+    Timeout,
+    Other,
+}
+
+impl InformixErrorKind {
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Self {
+        // An expired timeout interrupts the statement just like a cancel,
+        // with the same native code, so only the SQLSTATE tells them apart
+        match diagnostic.sqlstate.as_str() {
+            "HYT00" | "HYT01" => return InformixErrorKind::Timeout,
+            "HY008" => return InformixErrorKind::Cancelled,
+            _ => {}
+        }
+        // The ISAM code tells why a generic read or write error happened
+        if let Some(kind) = diagnostic.isam_code.and_then(Self::from_isam_code) {
+            return kind;
+        }
+        match diagnostic.native_code {
+            -239 | -268 | -100 => InformixErrorKind::UniqueViolation,
+            -691 | -692 => InformixErrorKind::ForeignKeyViolation,
+            -391 | -703 => InformixErrorKind::NotNullViolation,
+            -530 => InformixErrorKind::CheckViolation,
+            -263 | -289 | -107 | -113 | -144 | -154 => InformixErrorKind::LockTimeout,
+            -143 | -78 => InformixErrorKind::Deadlock,
+            -206 => InformixErrorKind::TableNotFound,
+            -217 => InformixErrorKind::ColumnNotFound,
+            -201 => InformixErrorKind::SyntaxError,
+            -908 | -930 | -25555 => InformixErrorKind::ConnectionFailed,
+            -25580 | -25582 | -1803 => InformixErrorKind::ConnectionLost,
+            -272 | -273 | -274 | -275 | -387 | -389 | -951 | -952 => InformixErrorKind::PermissionDenied,
+            -213 => InformixErrorKind::Cancelled,
+            _ => Self::from_sqlstate(&diagnostic.sqlstate),
+        }
+    }
+
+    fn from_isam_code(isam_code: i32) -> Option<Self> {
+        match isam_code {
+            -100 => Some(InformixErrorKind::UniqueViolation),
+            -107 | -113 | -144 | -154 => Some(InformixErrorKind::LockTimeout),
+            -143 => Some(InformixErrorKind::Deadlock),
+            _ => None,
+        }
+    }
+
+    fn from_sqlstate(sqlstate: &str) -> Self {
+        match sqlstate {
+            "23505" => InformixErrorKind::UniqueViolation,
+            "23503" => InformixErrorKind::ForeignKeyViolation,
+            "23502" => InformixErrorKind::NotNullViolation,
+            "40001" => InformixErrorKind::Deadlock,
+            "42S02" => InformixErrorKind::TableNotFound,
+            "42S22" => InformixErrorKind::ColumnNotFound,
+            "08001" | "08004" => InformixErrorKind::ConnectionFailed,
+            "08003" | "08S01" => InformixErrorKind::ConnectionLost,
+            "28000" => InformixErrorKind::PermissionDenied,
+            _ => InformixErrorKind::Other,
+        }
+    }
+
+    /// Whether retrying the same work later can succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            InformixErrorKind::LockTimeout | InformixErrorKind::Deadlock | InformixErrorKind::ConnectionLost
+        )
+    }
+}
+
+/// Errors returned by this crate.
+///
+/// Variants raised by a failing CLI call carry every diagnostic record the
+/// driver reported for it; the list is empty for errors detected on the Rust side.
 #[derive(Debug)]
 pub enum InformixError {
     HandleAllocationError(i32),
-    ConnectionError(String),
-    SQLExecutionError(String),
-    PrepareStatementError(String),
-    ParameterBindingError(String),
-    DataFetchError(String),
+    ConnectionError(String, Vec<Diagnostic>),
+    SQLExecutionError(String, Vec<Diagnostic>),
+    PrepareStatementError(String, Vec<Diagnostic>),
+    ParameterBindingError(String, Vec<Diagnostic>),
+    DataFetchError(String, Vec<Diagnostic>),
+    TransactionError(String, Vec<Diagnostic>),
+    /// A login timeout expired, or the client library gave up connecting once
+    /// `INFORMIXCONTIME` ran out.
+    ConnectTimeout(String, Vec<Diagnostic>),
+    /// Input rejected before reaching the driver, e.g. a string with a NUL byte.
+    InvalidInput(String),
+    /// No pooled connection became available within the acquire timeout.
+    PoolTimeout(Duration),
+    /// A command was sent to an `AsyncConnection` while a `RowStream` from it
+    /// was neither finished nor dropped.
+    ConnectionBusy,
+    /// The id does not name a statement prepared on the `AsyncConnection`'s worker.
+    StatementNotFound(u64),
+}
+
+impl InformixError {
+    /// The diagnostic records reported by the driver, most significant first.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            InformixError::HandleAllocationError(_)
+            | InformixError::InvalidInput(_)
+            | InformixError::PoolTimeout(_)
+            | InformixError::ConnectionBusy
+            | InformixError::StatementNotFound(_) => &[],
+            InformixError::ConnectionError(_, diagnostics)
+            | InformixError::SQLExecutionError(_, diagnostics)
+            | InformixError::PrepareStatementError(_, diagnostics)
+            | InformixError::ParameterBindingError(_, diagnostics)
+            | InformixError::DataFetchError(_, diagnostics)
+            | InformixError::TransactionError(_, diagnostics)
+            | InformixError::ConnectTimeout(_, diagnostics) => diagnostics,
+        }
+    }
+
+    /// Classifies the error from the first diagnostic record with a known code.
+    pub fn kind(&self) -> InformixErrorKind {
+        // Its -908 record alone would read as a refused connection
+        if let InformixError::ConnectTimeout(..) = self {
+            return InformixErrorKind::Timeout;
+        }
+        self.diagnostics().iter()
+            .map(InformixErrorKind::from_diagnostic)
+            .find(|kind| *kind != InformixErrorKind::Other)
+            .unwrap_or(InformixErrorKind::Other)
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+fn write_diagnostics(f: &mut fmt::Formatter<'_>, diagnostics: &[Diagnostic]) -> fmt::Result {
+    for (i, diagnostic) in diagnostics.iter().enumerate() {
+        write!(f, "{}{}", if i == 0 { ": " } else { "; " }, diagnostic)?;
+    }
+    Ok(())
 }
 
 impl fmt::Display for InformixError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            InformixError::HandleAllocationError(code) => write!(f, "Failed to allocate handle: {}", code),
-            InformixError::ConnectionError(msg) => write!(f, "Failed to connect: {}", msg),
-            InformixError::SQLExecutionError(msg) => write!(f, "SQL execution failed: {}", msg),
-            InformixError::PrepareStatementError(msg) => write!(f, "Failed to prepare statement: {}", msg),
-            InformixError::ParameterBindingError(msg) => write!(f, "Failed to bind parameter: {}", msg),
-            InformixError::DataFetchError(msg) => write!(f, "Failed to fetch data: {}", msg),
+            InformixError::HandleAllocationError(code) => write!(f, "Failed to allocate handle: {}", code)?,
+            InformixError::ConnectionError(msg, _) => write!(f, "Failed to connect: {}", msg)?,
+            InformixError::SQLExecutionError(msg, _) => write!(f, "SQL execution failed: {}", msg)?,
+            InformixError::PrepareStatementError(msg, _) => write!(f, "Failed to prepare statement: {}", msg)?,
+            InformixError::ParameterBindingError(msg, _) => write!(f, "Failed to bind parameter: {}", msg)?,
+            InformixError::DataFetchError(msg, _) => write!(f, "Failed to fetch data: {}", msg)?,
+            InformixError::TransactionError(msg, _) => write!(f, "Transaction failed: {}", msg)?,
+            InformixError::ConnectTimeout(msg, _) => write!(f, "Connection timed out: {}", msg)?,
+            InformixError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg)?,
+            InformixError::PoolTimeout(timeout) => write!(f, "Timed out after {:?} waiting for a pooled connection", timeout)?,
+            InformixError::ConnectionBusy => write!(f, "Connection is busy streaming the rows of another query")?,
+            InformixError::StatementNotFound(id) => write!(f, "Unknown prepared statement {}", id)?,
         }
+        write_diagnostics(f, self.diagnostics())
     }
 }
 
 impl Error for InformixError {}
 
-pub type Result<T> = std::result::Result<T, InformixError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, InformixError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(sqlstate: &str, native_code: i32, isam_code: Option<i32>) -> Diagnostic {
+        Diagnostic::new(sqlstate.to_string(), native_code, String::new(), isam_code)
+    }
+
+    #[test]
+    fn unique_violations_by_native_code() {
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("23000", -239, None)), InformixErrorKind::UniqueViolation);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("23000", -268, Some(-100))), InformixErrorKind::UniqueViolation);
+    }
+
+    #[test]
+    fn read_error_classified_by_isam_code() {
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY000", -244, Some(-107))), InformixErrorKind::LockTimeout);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY000", -244, Some(-111))), InformixErrorKind::Other);
+    }
+
+    #[test]
+    fn read_error_without_isam_code_is_other() {
+        for code in -246..=-243 {
+            let kind = InformixErrorKind::from_diagnostic(&diagnostic("HY000", code, None));
+            assert_eq!(kind, InformixErrorKind::Other);
+            assert!(!kind.is_retryable());
+        }
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY000", -244, Some(-154))), InformixErrorKind::LockTimeout);
+    }
+
+    #[test]
+    fn unknown_native_code_falls_back_to_sqlstate() {
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("23505", -1, None)), InformixErrorKind::UniqueViolation);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("08S01", -1, None)), InformixErrorKind::ConnectionLost);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("42S02", -1, None)), InformixErrorKind::TableNotFound);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY000", -1, None)), InformixErrorKind::Other);
+    }
+
+    #[test]
+    fn timeouts_and_cancels_by_sqlstate() {
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HYT00", -213, None)), InformixErrorKind::Timeout);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HYT01", -1, None)), InformixErrorKind::Timeout);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY008", -213, None)), InformixErrorKind::Cancelled);
+        assert_eq!(InformixErrorKind::from_diagnostic(&diagnostic("HY000", -213, None)), InformixErrorKind::Cancelled);
+        assert!(!InformixErrorKind::Timeout.is_retryable());
+        assert!(!InformixErrorKind::Cancelled.is_retryable());
+    }
+
+    #[test]
+    fn isam_message_parsed_from_driver_text() {
+        let diagnostic = Diagnostic::new(
+            "HY000".to_string(),
+            -244,
+            "Could not do a physical-order read to fetch next row. ISAM error: record is locked.".to_string(),
+            Some(-107),
+        );
+        assert_eq!(diagnostic.isam_code, Some(-107));
+        assert_eq!(diagnostic.isam_message.as_deref(), Some("record is locked"));
+        assert_eq!(
+            diagnostic.to_string(),
+            "SQLSTATE = HY000, Native Error = -244, Message = Could not do a physical-order read to fetch next row. \
+             ISAM error: record is locked., ISAM Error = -107 (record is locked)"
+        );
+    }
+
+    #[test]
+    fn isam_message_falls_back_to_known_codes() {
+        let diagnostic = Diagnostic::new("HY000".to_string(), -206, "The specified table is not in the database.".to_string(), Some(-111));
+        assert_eq!(diagnostic.isam_message.as_deref(), Some("no record found"));
+
+        let diagnostic = Diagnostic::new("HY000".to_string(), -206, "ISAM error: ".to_string(), Some(-111));
+        assert_eq!(diagnostic.isam_message.as_deref(), Some("no record found"));
+
+        let diagnostic = Diagnostic::new("HY000".to_string(), -244, "Read failed.".to_string(), Some(-9999));
+        assert_eq!(diagnostic.isam_message, None);
+        assert!(diagnostic.to_string().ends_with(", ISAM Error = -9999"));
+    }
+
+    #[test]
+    fn no_isam_message_without_isam_code() {
+        let diagnostic = Diagnostic::new("HY000".to_string(), -244, "ISAM error: record is locked.".to_string(), None);
+        assert_eq!(diagnostic.isam_message, None);
+        assert!(!diagnostic.to_string().contains("ISAM Error"));
+    }
+
+    #[test]
+    fn error_kind_uses_first_classified_record() {
+        let error = InformixError::SQLExecutionError(
+            "result = -1".to_string(),
+            vec![diagnostic("HY000", -1, None), diagnostic("40001", -143, None)],
+        );
+        assert_eq!(error.kind(), InformixErrorKind::Deadlock);
+        assert!(error.is_retryable());
+        assert_eq!(InformixError::InvalidInput("sql".to_string()).kind(), InformixErrorKind::Other);
+    }
+
+    #[test]
+    fn connect_timeout_is_not_a_refused_connection() {
+        let refused = vec![diagnostic("08001", -908, None)];
+        assert_eq!(
+            InformixError::ConnectionError("result = -1".to_string(), refused.clone()).kind(),
+            InformixErrorKind::ConnectionFailed,
+        );
+        assert_eq!(InformixError::ConnectTimeout("gave up".to_string(), refused).kind(), InformixErrorKind::Timeout);
+    }
+}
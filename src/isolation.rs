@@ -0,0 +1,66 @@
+use std::os::raw::c_ulong;
+use crate::{SQL_TXN_READ_COMMITTED, SQL_TXN_READ_UNCOMMITTED, SQL_TXN_REPEATABLE_READ, SQL_TXN_SERIALIZABLE};
+
+/// Informix isolation levels, set with `Connection::set_isolation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    DirtyRead,
+    DirtyReadRetainUpdateLocks,
+    CommittedRead,
+    CommittedReadRetainUpdateLocks,
+    CommittedReadLastCommitted,
+    CursorStability,
+    CursorStabilityRetainUpdateLocks,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// The `SQL_ATTR_TXN_ISOLATION` value for levels the driver understands directly.
+    pub(crate) fn txn_isolation(self) -> Option<c_ulong> {
+        match self {
+            IsolationLevel::DirtyRead => Some(SQL_TXN_READ_UNCOMMITTED),
+            IsolationLevel::CommittedRead => Some(SQL_TXN_READ_COMMITTED),
+            IsolationLevel::RepeatableRead => Some(SQL_TXN_REPEATABLE_READ),
+            IsolationLevel::Serializable => Some(SQL_TXN_SERIALIZABLE),
+            _ => None,
+        }
+    }
+
+    /// The Informix statement that selects this level.
+    pub fn to_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::DirtyRead => "SET ISOLATION TO DIRTY READ",
+            IsolationLevel::DirtyReadRetainUpdateLocks => "SET ISOLATION TO DIRTY READ RETAIN UPDATE LOCKS",
+            IsolationLevel::CommittedRead => "SET ISOLATION TO COMMITTED READ",
+            IsolationLevel::CommittedReadRetainUpdateLocks => "SET ISOLATION TO COMMITTED READ RETAIN UPDATE LOCKS",
+            IsolationLevel::CommittedReadLastCommitted => "SET ISOLATION TO COMMITTED READ LAST COMMITTED",
+            IsolationLevel::CursorStability => "SET ISOLATION TO CURSOR STABILITY",
+            IsolationLevel::CursorStabilityRetainUpdateLocks => "SET ISOLATION TO CURSOR STABILITY RETAIN UPDATE LOCKS",
+            IsolationLevel::RepeatableRead => "SET ISOLATION TO REPEATABLE READ",
+            IsolationLevel::Serializable => "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+        }
+    }
+}
+
+/// How a session waits for locks held by others, set with `Connection::set_lock_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Fail immediately when a lock is held (the Informix default).
+    NotWait,
+    /// Wait until the lock is released.
+    Wait,
+    /// Wait at most the given number of seconds.
+    WaitSeconds(u32),
+}
+
+impl LockMode {
+    /// The Informix statement that selects this mode.
+    pub fn to_sql(self) -> String {
+        match self {
+            LockMode::NotWait => "SET LOCK MODE TO NOT WAIT".to_string(),
+            LockMode::Wait => "SET LOCK MODE TO WAIT".to_string(),
+            LockMode::WaitSeconds(seconds) => format!("SET LOCK MODE TO WAIT {}", seconds),
+        }
+    }
+}
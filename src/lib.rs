@@ -1,11 +1,56 @@
 // File: src/lib.rs
-use chrono::NaiveDate;
-use chrono::Datelike;
-use std::mem;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Datelike, Timelike};
 use std::os::raw::{c_char, c_uchar, c_int, c_void, c_short, c_ushort, c_long, c_ulong};
-use std::ffi::{CStr, CString};
+use std::cell::{OnceCell, RefCell};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::ffi::CString;
+#[macro_use]
+mod logging;
+#[cfg(any(feature = "deadpool", feature = "bb8"))]
+mod blocking;
+#[cfg(feature = "async")]
+pub mod async_connection;
+#[cfg(feature = "bb8")]
+pub mod bb8;
+pub mod cancel;
+pub mod column;
+#[cfg(feature = "deadpool")]
+pub mod deadpool;
+pub mod environment;
 pub mod errors;
-use errors::{InformixError, Result};
+pub mod isolation;
+pub mod options;
+pub mod params;
+pub mod pool;
+#[cfg(feature = "r2d2")]
+pub mod r2d2;
+pub mod row;
+pub mod shared;
+pub mod transaction;
+pub mod types;
+pub mod value;
+pub mod warnings;
+use errors::{Diagnostic, InformixError, Result};
+pub use cancel::CancelHandle;
+pub use column::ColumnDesc;
+pub use environment::Environment;
+pub use isolation::{IsolationLevel, LockMode};
+pub use options::ConnectOptions;
+pub use logging::redact_connection_string;
+pub use params::SqlParam;
+pub use pool::{ConnectionManager, InformixManager, Pool, PoolOptions, PooledConnection};
+pub use row::Row;
+pub use shared::SharedConnection;
+pub use transaction::{Savepoint, Transaction};
+pub use types::FromSql;
+pub use value::Value;
+pub use warnings::{log_warning, WarningHook};
+use cancel::CancelTarget;
+use warnings::Warnings;
 
 
 #[link(name = "ifcli")]
@@ -21,6 +66,9 @@ extern "C" {
         ValueType: c_short, ParameterType: c_short, ColumnSize: c_ulong,
         DecimalDigits: c_short, ParameterValuePtr: *const c_void,
         BufferLength: c_long, StrLen_or_IndPtr: *const c_long) -> c_short;
+    fn SQLDescribeParam(StatementHandle: *mut c_void, ParameterNumber: c_ushort,
+        DataTypePtr: *mut c_short, ParameterSizePtr: *mut c_ulong,
+        DecimalDigitsPtr: *mut c_short, NullablePtr: *mut c_short) -> c_short;
     fn SQLExecute(StatementHandle: *mut c_void) -> c_short;
     fn SQLExecDirect(StatementHandle: *mut c_void, StatementText: *const c_char, TextLength: c_int) -> c_int;
     fn SQLFetch(StatementHandle: *mut c_void) -> c_int;
@@ -30,6 +78,17 @@ extern "C" {
         SQLState: *mut c_char, NativeErrorPtr: *mut c_int,
         MessageText: *mut c_char, BufferLength: c_short,
         TextLengthPtr: *mut c_short) -> c_short;
+    fn SQLGetDiagField(HandleType: c_short, Handle: *mut c_void, RecNumber: c_short,
+        DiagIdentifier: c_short, DiagInfoPtr: *mut c_void, BufferLength: c_short,
+        StringLengthPtr: *mut c_short) -> c_short;
+    fn SQLNumResultCols(StatementHandle: *mut c_void, ColumnCountPtr: *mut c_short) -> c_short;
+    fn SQLDescribeCol(StatementHandle: *mut c_void, ColumnNumber: c_ushort,
+        ColumnName: *mut c_uchar, BufferLength: c_short, NameLengthPtr: *mut c_short,
+        DataTypePtr: *mut c_short, ColumnSizePtr: *mut c_ulong,
+        DecimalDigitsPtr: *mut c_short, NullablePtr: *mut c_short) -> c_short;
+    fn SQLColAttribute(StatementHandle: *mut c_void, ColumnNumber: c_ushort, FieldIdentifier: c_ushort,
+        CharacterAttributePtr: *mut c_void, BufferLength: c_short,
+        StringLengthPtr: *mut c_short, NumericAttributePtr: *mut c_long) -> c_short;
     fn SQLDriverConnect(
         ConnectionHandle: *mut c_void,
         WindowHandle: *mut c_void,
@@ -39,6 +98,17 @@ extern "C" {
         BufferLength: c_short,
         StringLength2Ptr: *mut c_short,
         DriverCompletion: c_ushort) -> c_short;
+    fn SQLSetConnectAttr(ConnectionHandle: *mut c_void, Attribute: c_int,
+        ValuePtr: *mut c_void, StringLength: c_int) -> c_short;
+    fn SQLGetConnectAttr(ConnectionHandle: *mut c_void, Attribute: c_int,
+        ValuePtr: *mut c_void, BufferLength: c_int, StringLengthPtr: *mut c_int) -> c_short;
+    fn SQLSetEnvAttr(EnvironmentHandle: *mut c_void, Attribute: c_int,
+        ValuePtr: *mut c_void, StringLength: c_int) -> c_short;
+    fn SQLEndTran(HandleType: c_short, Handle: *mut c_void, CompletionType: c_short) -> c_short;
+    fn SQLFreeStmt(StatementHandle: *mut c_void, Option: c_ushort) -> c_short;
+    fn SQLSetStmtAttr(StatementHandle: *mut c_void, Attribute: c_int,
+                      Value: *mut c_void, StringLength: c_int) -> c_short;
+    fn SQLCancel(StatementHandle: *mut c_void) -> c_short;
     fn SQLDisconnect(ConnectionHandle: *mut c_void) -> c_int;
     fn SQLFreeHandle(HandleType: c_short, Handle: *mut c_void) -> c_int;
 }
@@ -76,8 +146,56 @@ pub const SQL_INTEGER: c_short = 4;
 pub const SQL_C_SHORT: c_short = 5;
 pub const SQL_SMALLINT: c_short = 5;
 
+// SQL data types reported by SQLDescribeCol
+pub const SQL_CHAR: c_short = 1;
+pub const SQL_NUMERIC: c_short = 2;
+pub const SQL_DECIMAL: c_short = 3;
+pub const SQL_FLOAT: c_short = 6;
+pub const SQL_REAL: c_short = 7;
+pub const SQL_DOUBLE: c_short = 8;
+pub const SQL_TYPE_TIME: c_short = 92;
+pub const SQL_TYPE_TIMESTAMP: c_short = 93;
+pub const SQL_LONGVARCHAR: c_short = -1;
+pub const SQL_BINARY: c_short = -2;
+pub const SQL_VARBINARY: c_short = -3;
+pub const SQL_LONGVARBINARY: c_short = -4;
+pub const SQL_BIGINT: c_short = -5;
+pub const SQL_BIT: c_short = -7;
+pub const SQL_WCHAR: c_short = -8;
+pub const SQL_WVARCHAR: c_short = -9;
+pub const SQL_WLONGVARCHAR: c_short = -10;
+pub const SQL_INTERVAL_YEAR: c_short = 101;
+pub const SQL_INTERVAL_MINUTE_TO_SECOND: c_short = 113;
+// Informix-specific SQL data types
+pub const SQL_INFX_UDT_LVARCHAR: c_short = -104;
+pub const SQL_INFX_BIGINT: c_short = -114;
+
+// C data types used when fetching typed values
+pub const SQL_C_FLOAT: c_short = 7;
+pub const SQL_C_DOUBLE: c_short = 8;
+pub const SQL_C_BIT: c_short = -7;
+pub const SQL_C_BINARY: c_short = -2;
+pub const SQL_C_SBIGINT: c_short = -25;
+pub const SQL_C_TYPE_DATE: c_short = 91;
+pub const SQL_C_TYPE_TIME: c_short = 92;
+pub const SQL_C_TYPE_TIMESTAMP: c_short = 93;
+
+// Column attributes and nullability
+pub const SQL_DESC_TYPE_NAME: c_ushort = 14;
+pub const SQL_DESC_PRECISION: c_ushort = 1005;
+pub const SQL_DESC_SCALE: c_ushort = 1006;
+pub const SQL_NO_NULLS: c_short = 0;
+pub const SQL_NULLABLE: c_short = 1;
+
+// Informix-specific diagnostic fields
+pub const SQL_DIAG_ISAM_ERROR: c_short = 13;
+
 // SQL special values
 pub const SQL_NULL_DATA: c_long = -1;
+pub const SQL_NO_TOTAL: c_long = -4;
+
+// Size of the first buffer handed to SQLGetData for each column
+const GET_DATA_CHUNK_SIZE: usize = 2048;
 
 // SQL handle types
 pub const SQL_HANDLE_ENV: c_short = 1;
@@ -85,44 +203,146 @@ pub const SQL_HANDLE_DBC: c_short = 2;
 pub const SQL_HANDLE_STMT: c_short = 3;
 
 pub const SQL_DRIVER_NOPROMPT: c_ushort = 0;
+// Environment attributes
+pub const SQL_ATTR_ODBC_VERSION: c_int = 200;
+pub const SQL_OV_ODBC3: c_ulong = 3;
+
+// Connection attributes
+pub const SQL_ATTR_AUTOCOMMIT: c_int = 102;
+pub const SQL_ATTR_LOGIN_TIMEOUT: c_int = 103;
+pub const SQL_ATTR_CONNECTION_TIMEOUT: c_int = 113;
+pub const SQL_AUTOCOMMIT_OFF: c_ulong = 0;
+pub const SQL_AUTOCOMMIT_ON: c_ulong = 1;
+pub const SQL_ATTR_TXN_ISOLATION: c_int = 108;
+pub const SQL_TXN_READ_UNCOMMITTED: c_ulong = 1;
+pub const SQL_TXN_READ_COMMITTED: c_ulong = 2;
+pub const SQL_TXN_REPEATABLE_READ: c_ulong = 4;
+pub const SQL_TXN_SERIALIZABLE: c_ulong = 8;
+pub const SQL_ATTR_CONNECTION_DEAD: c_int = 1209;
+pub const SQL_CD_TRUE: c_ulong = 1;
+pub const SQL_CD_FALSE: c_ulong = 0;
+
+// Transaction completion types
+pub const SQL_COMMIT: c_short = 0;
+pub const SQL_ROLLBACK: c_short = 1;
+
 // Other SQL constants
 pub const SQL_PARAM_INPUT: c_short = 1;
 pub const SQL_NTS: c_long = -3;
+pub const SQL_CLOSE: c_ushort = 0;
+pub const SQL_ATTR_QUERY_TIMEOUT: c_int = 0;
+
+/// Converts `value` for a CLI call, rejecting interior NUL bytes.
+///
+/// `what` names the input in the error message.
+pub(crate) fn to_cstring(value: &str, what: &str) -> Result<CString> {
+    CString::new(value).map_err(|e| InformixError::InvalidInput(
+        format!("Invalid {}: NUL byte at position {}", what, e.nul_position())
+    ))
+}
+
+/// Converts a timeout to the whole seconds the driver expects, rounding up so
+/// that a short timeout does not become 0 (no timeout).
+pub(crate) fn timeout_seconds(timeout: Duration) -> c_ulong {
+    let seconds = timeout.as_secs().saturating_add(u64::from(timeout.subsec_nanos() > 0));
+    c_ulong::try_from(seconds).unwrap_or(c_ulong::MAX)
+}
+
+/// Collects every diagnostic record attached to `handle` with `SQLGetDiagRec`.
+pub(crate) fn get_diagnostics(handle_type: c_short, handle: *mut c_void) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for record in 1.. {
+        let mut state = [0u8; 6];
+        let mut native_error: c_int = 0;
+        let mut message = [0u8; 1024];
+        let mut out_len: c_short = 0;
+        let result = unsafe {
+            SQLGetDiagRec(
+                handle_type,
+                handle,
+                record,
+                state.as_mut_ptr() as *mut c_char,
+                &mut native_error,
+                message.as_mut_ptr() as *mut c_char,
+                message.len() as c_short,
+                &mut out_len,
+            )
+        };
+        if result != SQL_SUCCESS && result != SQL_SUCCESS_WITH_INFO {
+            break;
+        }
+        let message_len = (out_len.max(0) as usize).min(message.len() - 1);
+
+        // Informix reports the underlying ISAM error in a driver-specific field
+        let mut isam_error: c_int = 0;
+        let result = unsafe {
+            SQLGetDiagField(
+                handle_type,
+                handle,
+                record,
+                SQL_DIAG_ISAM_ERROR,
+                &mut isam_error as *mut c_int as *mut c_void,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        let isam_code = if (result == SQL_SUCCESS || result == SQL_SUCCESS_WITH_INFO) && isam_error != 0 {
+            Some(isam_error)
+        } else {
+            None
+        };
+
+        diagnostics.push(Diagnostic::new(
+            String::from_utf8_lossy(&state[..5]).into_owned(),
+            native_error,
+            String::from_utf8_lossy(&message[..message_len]).into_owned(),
+            isam_code,
+        ));
+    }
+    diagnostics
+}
 
 // Safe Rust wrappers
 pub struct Connection {
     handle: *mut c_void,
+    warnings: Warnings,
+    // Dropped after the DBC handle is freed, keeping the parent ENV handle valid until then
+    _env: Arc<Environment>,
 }
 
 
 impl Connection {
+    /// Creates a connection in its own, private environment.
     pub fn new() -> Result<Self> {
-        let mut handle: *mut c_void = std::ptr::null_mut();
+        Connection::with_environment(&Environment::new_shared()?)
+    }
+
+    /// Creates a connection whose DBC handle is allocated from `env`.
+    pub fn with_environment(env: &Arc<Environment>) -> Result<Self> {
+        let mut conn_handle: *mut c_void = std::ptr::null_mut();
         let result = unsafe {
-            SQLAllocHandle(SQL_HANDLE_ENV.into(), std::ptr::null_mut(), &mut handle)
+            SQLAllocHandle(SQL_HANDLE_DBC.into(), env.handle, &mut conn_handle)
         };
         if result == 0 {
-            let mut conn_handle: *mut c_void = std::ptr::null_mut();
-            let conn_result = unsafe {
-                SQLAllocHandle(SQL_HANDLE_DBC.into(), handle, &mut conn_handle)
-            };
-            if conn_result == 0 {
-                Ok(Connection { handle: conn_handle })
-            } else {
-                Err(InformixError::HandleAllocationError(conn_result))
-            }
+            Ok(Connection { handle: conn_handle, warnings: Warnings::default(), _env: Arc::clone(env) })
         } else {
             Err(InformixError::HandleAllocationError(result))
         }
     }
 
     pub fn connect_with_string(&self, conn_string: &str) -> Result<()> {
-        println!("Attempting to connect with string: {}", conn_string);
-        
-        let conn_string = CString::new(conn_string).map_err(|e| InformixError::ConnectionError(format!("Invalid connection string: {}", e)))?;
+        self.driver_connect(conn_string, options::env_connect_time())
+    }
+
+    /// Connects with `SQLDriverConnect`; `connect_time` is the `INFORMIXCONTIME`
+    /// in effect, used to recognize a connect that gave up.
+    fn driver_connect(&self, conn_string: &str, connect_time: Option<Duration>) -> Result<()> {
+        debug!(conn_string = %redact_connection_string(conn_string), "connecting");
+        let conn_string = to_cstring(conn_string, "connection string")?;
         
         let mut out_conn_string = [0u8; 1024];
         let mut out_conn_string_len: c_short = 0;
+        let started = Instant::now();
         let result = unsafe {
             SQLDriverConnect(
                 self.handle,
@@ -136,16 +356,24 @@ impl Connection {
             )
         };
         
-        if result == SQL_SUCCESS as c_short || result == SQL_SUCCESS_WITH_INFO as c_short {
-            println!("Successfully connected to the database");
+        if self.succeeded(result) {
+            debug!("connected");
             Ok(())
         } else {
-            let error_message = self.get_error_message();
-            Err(InformixError::ConnectionError(format!("Failed to connect: result = {}, {}", result, error_message)))
+            let error = options::connect_error(
+                format!("result = {}", result),
+                self.get_diagnostics(),
+                started.elapsed(),
+                connect_time,
+            );
+            warn!(error = %error, "connection failed");
+            Err(error)
         }
     }
 
-    pub fn prepare(&self, sql: &str) -> Result<Statement> {
+    pub fn prepare(&self, sql: &str) -> Result<Statement<'_>> {
+        // Validate before allocating so a rejected string leaks no handle
+        let sql_cstring = to_cstring(sql, "SQL string")?;
         let mut stmt_handle: *mut c_void = std::ptr::null_mut();
         let result = unsafe {
             SQLAllocHandle(SQL_HANDLE_STMT.into(), self.handle, &mut stmt_handle)
@@ -154,284 +382,806 @@ impl Connection {
             return Err(InformixError::HandleAllocationError(result));
         }
 
-        let sql_cstring = CString::new(sql)
-            .map_err(|e| InformixError::PrepareStatementError(format!("Invalid SQL string: {}", e)))?;
         let result = unsafe {
             SQLPrepare(stmt_handle, sql_cstring.as_ptr() as *const c_uchar, sql.len() as c_int)
         };
         if result != 0 {
+            let diagnostics = get_diagnostics(SQL_HANDLE_STMT, stmt_handle);
             unsafe { SQLFreeHandle(SQL_HANDLE_STMT, stmt_handle) };
-            return Err(InformixError::PrepareStatementError(format!("Failed to prepare SQL: {}", result)));
+            return Err(InformixError::PrepareStatementError(format!("Failed to prepare SQL: {}", result), diagnostics));
+        }
+
+        debug!(sql, "prepared statement");
+        Ok(self.new_statement(stmt_handle, sql))
+    }
+
+    /// Connects like `connect_with_string`, applying the timeouts in `options` first.
+    ///
+    /// An expired login timeout fails with `InformixError::ConnectTimeout`, as
+    /// does running out of `INFORMIXCONTIME`, which is detected on a
+    /// best-effort basis from how long the connect took. A request that runs
+    /// out of the connection timeout later fails with an error of kind
+    /// `InformixErrorKind::Timeout`.
+    pub fn connect_with_options(&self, conn_string: &str, options: &ConnectOptions) -> Result<()> {
+        if let Some(timeout) = options.login_timeout {
+            self.set_timeout_attribute(SQL_ATTR_LOGIN_TIMEOUT, timeout, "login timeout")?;
+        }
+        if let Some(timeout) = options.connection_timeout {
+            self.set_timeout_attribute(SQL_ATTR_CONNECTION_TIMEOUT, timeout, "connection timeout")?;
         }
+        self.driver_connect(&options.connection_string(conn_string), options.connect_time())
+    }
 
-        Ok(Statement::new(stmt_handle , ""))
+    fn set_timeout_attribute(&self, attribute: c_int, timeout: Duration, what: &str) -> Result<()> {
+        let result = unsafe {
+            SQLSetConnectAttr(self.handle, attribute, timeout_seconds(timeout) as *mut c_void, 0)
+        };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            Err(InformixError::ConnectionError(format!("Failed to set {}", what), self.get_diagnostics()))
+        }
     }
 
     pub fn connect(&self, server: &str, user: &str, password: &str) -> Result<()> {
-        let server = CString::new(server).unwrap();
-        let user = CString::new(user).unwrap();
-        let password = CString::new(password).unwrap();
+        let server = to_cstring(server, "server name")?;
+        let user = to_cstring(user, "user name")?;
+        let password = to_cstring(password, "password")?;
         let result = unsafe {
             SQLConnect(self.handle, 
                        server.as_ptr(), server.as_bytes().len() as c_int,
                        user.as_ptr(), user.as_bytes().len() as c_int,
                        password.as_ptr(), password.as_bytes().len() as c_int)
         };
-        if result == 0 {
+        if self.succeeded(result as c_short) {
             Ok(())
         } else {
-            let error_message = self.get_error_message();
-            Err(InformixError::ConnectionError(error_message))
+            Err(InformixError::ConnectionError(format!("result = {}", result), self.get_diagnostics()))
         }
     }
 
-    fn get_error_message(&self) -> String {
-        let mut state = [0i8; 6];
-        let mut native_error = 0i32;
-        let mut message = [0i8; 1024];
-        let mut out_len = 0i16;
-        
-        unsafe {
-            SQLGetDiagRec(SQL_HANDLE_DBC, self.handle, 1, 
-                          state.as_mut_ptr() as *mut c_char, 
-                          &mut native_error, 
-                          message.as_mut_ptr() as *mut c_char, 
-                          message.len() as c_short, 
-                          &mut out_len);
+    fn get_diagnostics(&self) -> Vec<Diagnostic> {
+        get_diagnostics(SQL_HANDLE_DBC, self.handle)
+    }
+
+    /// Checks a CLI return code, recording the diagnostics of `SQL_SUCCESS_WITH_INFO` as warnings.
+    fn succeeded(&self, result: c_short) -> bool {
+        if result == SQL_SUCCESS_WITH_INFO {
+            self.warnings.record(SQL_HANDLE_DBC, self.handle);
         }
-        
-        let state = unsafe { CStr::from_ptr(state.as_ptr() as *const c_char) }.to_string_lossy();
-        let message = unsafe { CStr::from_ptr(message.as_ptr() as *const c_char) }.to_string_lossy();
-        
-        format!("SQLSTATE = {}, Native Error = {}, Message = {}", state, native_error, message)
+        result == SQL_SUCCESS || result == SQL_SUCCESS_WITH_INFO
+    }
+
+    /// Returns the warnings received so far by this connection, such as an
+    /// ANSI-mode database or a database without logging being opened.
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        self.warnings.get()
+    }
+
+    pub fn clear_warnings(&self) {
+        self.warnings.clear();
+    }
+
+    /// Calls `hook` for every warning received by this connection and by
+    /// statements created after the hook is set.
+    pub fn set_warning_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&Diagnostic) + Send + Sync + 'static,
+    {
+        self.warnings.set_hook(Arc::new(hook));
+    }
+
+    fn new_statement(&self, handle: *mut c_void, sql: &str) -> Statement<'_> {
+        let mut stmt = Statement::new(handle, sql);
+        stmt.warnings = Warnings::new(self.warnings.hook());
+        stmt
     }
 
-    pub fn execute(&self, sql: &str) -> Result<Statement> {
+    pub fn execute(&self, sql: &str) -> Result<Statement<'_>> {
+        // Validate before allocating so a rejected string leaks no handle
+        let sql_cstring = to_cstring(sql, "SQL string")?;
         let mut stmt_handle: *mut c_void = std::ptr::null_mut();
         let result = unsafe {
-            SQLAllocHandle(3, self.handle, &mut stmt_handle)
+            SQLAllocHandle(SQL_HANDLE_STMT.into(), self.handle, &mut stmt_handle)
         };
         if result != 0 {
             return Err(InformixError::HandleAllocationError(result));
         }
 
-        let sql = CString::new(sql).unwrap();
         let result = unsafe {
-            SQLExecDirect(stmt_handle, sql.as_ptr(), sql.as_bytes().len() as c_int)
+            SQLExecDirect(stmt_handle, sql_cstring.as_ptr(), sql_cstring.as_bytes().len() as c_int)
         };
-        if result == 0 {
-            Ok(Statement::new(stmt_handle , ""))
+        debug!(sql, result, "executed statement");
+        // Under ODBC 3 a searched UPDATE or DELETE that matches no rows returns SQL_NO_DATA
+        if result == SQL_SUCCESS.into() || result == SQL_SUCCESS_WITH_INFO.into() || result == SQL_NO_DATA.into() {
+            let stmt = self.new_statement(stmt_handle, sql);
+            if result == SQL_SUCCESS_WITH_INFO.into() {
+                stmt.warnings.record(SQL_HANDLE_STMT, stmt_handle);
+            }
+            Ok(stmt)
         } else {
-            unsafe { SQLFreeHandle(3, stmt_handle) };
-            Err(InformixError::SQLExecutionError(format!("Failed to execute SQL: {}", result)))
+            let diagnostics = get_diagnostics(SQL_HANDLE_STMT, stmt_handle);
+            unsafe { SQLFreeHandle(SQL_HANDLE_STMT, stmt_handle) };
+            Err(InformixError::SQLExecutionError(format!("Failed to execute SQL: {}", result), diagnostics))
         }
     }
+
+    /// Asks the driver whether the connection to the server has been lost,
+    /// using `SQL_ATTR_CONNECTION_DEAD`. No round trip to the server is made.
+    pub fn is_dead(&self) -> Result<bool> {
+        let mut value: u32 = 0;
+        let result = unsafe {
+            SQLGetConnectAttr(
+                self.handle,
+                SQL_ATTR_CONNECTION_DEAD,
+                &mut value as *mut u32 as *mut c_void,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if self.succeeded(result) {
+            Ok(value as c_ulong == SQL_CD_TRUE)
+        } else {
+            Err(InformixError::ConnectionError("Failed to read connection state".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Returns whether each statement is committed as soon as it completes.
+    pub fn autocommit(&self) -> Result<bool> {
+        // SQL_ATTR_AUTOCOMMIT is a 32-bit SQLUINTEGER
+        let mut value: u32 = 0;
+        let result = unsafe {
+            SQLGetConnectAttr(
+                self.handle,
+                SQL_ATTR_AUTOCOMMIT,
+                &mut value as *mut u32 as *mut c_void,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if self.succeeded(result) {
+            Ok(value as c_ulong == SQL_AUTOCOMMIT_ON)
+        } else {
+            Err(InformixError::TransactionError("Failed to read autocommit".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Enables or disables autocommit with `SQL_ATTR_AUTOCOMMIT`.
+    ///
+    /// With autocommit off the driver opens a transaction implicitly, both in
+    /// logged databases (BEGIN WORK) and in ANSI-mode databases.
+    pub fn set_autocommit(&self, enabled: bool) -> Result<()> {
+        let value = if enabled { SQL_AUTOCOMMIT_ON } else { SQL_AUTOCOMMIT_OFF };
+        let result = unsafe {
+            SQLSetConnectAttr(self.handle, SQL_ATTR_AUTOCOMMIT, value as *mut c_void, 0)
+        };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            Err(InformixError::TransactionError("Failed to set autocommit".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Commits the current transaction.
+    pub fn commit(&self) -> Result<()> {
+        self.end_transaction(SQL_COMMIT)
+    }
+
+    /// Rolls back the current transaction.
+    pub fn rollback(&self) -> Result<()> {
+        self.end_transaction(SQL_ROLLBACK)
+    }
+
+    fn end_transaction(&self, completion_type: c_short) -> Result<()> {
+        let result = unsafe { SQLEndTran(SQL_HANDLE_DBC, self.handle, completion_type) };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            let action = if completion_type == SQL_COMMIT { "commit" } else { "roll back" };
+            Err(InformixError::TransactionError(format!("Failed to {}", action), self.get_diagnostics()))
+        }
+    }
+
+    /// Sets the isolation level for the session.
+    ///
+    /// Levels with an ODBC equivalent go through `SQL_ATTR_TXN_ISOLATION`; the
+    /// Informix-specific ones are set with `SET ISOLATION`.
+    pub fn set_isolation(&self, level: IsolationLevel) -> Result<()> {
+        let Some(value) = level.txn_isolation() else {
+            self.execute(level.to_sql())?;
+            return Ok(());
+        };
+        let result = unsafe {
+            SQLSetConnectAttr(self.handle, SQL_ATTR_TXN_ISOLATION, value as *mut c_void, 0)
+        };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            Err(InformixError::TransactionError("Failed to set isolation level".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Sets how the session waits for locks held by other sessions.
+    pub fn set_lock_mode(&self, mode: LockMode) -> Result<()> {
+        self.execute(&mode.to_sql())?;
+        Ok(())
+    }
+
+    /// Starts a transaction by turning autocommit off.
+    ///
+    /// The returned guard rolls the transaction back when dropped unless
+    /// `Transaction::commit` was called. The connection stays borrowed
+    /// exclusively until then, so transactions cannot overlap.
+    pub fn transaction(&mut self) -> Result<Transaction<'_>> {
+        Transaction::begin(self)
+    }
 }
 
+// SAFETY: Informix CLI handles are thread-safe: the driver serializes calls made
+// on the same DBC handle, so the handle may be used from whichever thread owns
+// the `Connection`. It is not `Sync` because the warning list is a `RefCell`;
+// use `SharedConnection` to share one connection between threads.
+unsafe impl Send for Connection {}
+
 impl Drop for Connection {
     fn drop(&mut self) {
         unsafe {
             SQLDisconnect(self.handle);
-            SQLFreeHandle(1, self.handle);
+            SQLFreeHandle(SQL_HANDLE_DBC, self.handle);
         }
     }
 }
 
-pub struct Statement {
-    pub handle: *mut c_void,
+/// A statement handle allocated from a `Connection`.
+///
+/// The `'conn` lifetime ties the statement to its connection, so it cannot be
+/// used after the connection is dropped and its DBC handle freed.
+pub struct Statement<'conn> {
+    handle: *mut c_void,
     query: String,
+    columns: OnceCell<Vec<ColumnDesc>>,
+    // Buffers referenced by the driver for bound parameters, keyed by parameter number
+    params: RefCell<BTreeMap<u16, Box<SqlParam>>>,
+    warnings: Warnings,
+    // Shared with `CancelHandle`s, which must not outlive the handle
+    cancel: Arc<CancelTarget>,
+    _conn: PhantomData<&'conn Connection>,
 }
 
-impl Statement {
-    pub fn new(handle: *mut c_void, query: &str) -> Self {
+impl<'conn> Statement<'conn> {
+    /// Takes ownership of a STMT handle allocated from the connection that
+    /// `'conn` borrows; the handle is freed when the statement is dropped.
+    pub(crate) fn new(handle: *mut c_void, query: &str) -> Self {
         Statement {
             handle,
             query: query.into(),
+            columns: OnceCell::new(),
+            params: RefCell::new(BTreeMap::new()),
+            warnings: Warnings::default(),
+            cancel: Arc::new(CancelTarget::new(handle)),
+            _conn: PhantomData,
         }
     }
 
-    pub fn bind_parameter<T: ToSql>(&self, param_num: u16, value: &T) -> Result<()> {
-        value.bind_parameter(self.handle, param_num)
+    /// The raw STMT handle, for CLI calls this crate does not wrap.
+    ///
+    /// The statement keeps ownership: the handle must not be freed, and it is
+    /// invalid once the statement is dropped.
+    pub fn handle(&self) -> *mut c_void {
+        self.handle
+    }
+
+    /// The SQL text this statement was prepared or executed with.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Binds `value` to parameter `param_num` (1-based).
+    ///
+    /// The statement takes ownership of the converted buffer, replacing any
+    /// buffer previously bound to the same parameter.
+    pub fn bind_parameter<T: ToSql + ?Sized>(&self, param_num: u16, value: &T) -> Result<()> {
+        let mut param = Box::new(value.to_sql()?);
+        if param.is_untyped() {
+            // An untyped NULL takes the parameter's own type when the driver can tell
+            if let Some((sql_type, column_size, decimal_digits)) = self.describe_parameter(param_num) {
+                param.sql_type = sql_type;
+                param.column_size = column_size.max(1);
+                param.decimal_digits = decimal_digits;
+            }
+        }
+        let result = unsafe {
+            SQLBindParameter(
+                self.handle,
+                param_num as c_ushort,
+                SQL_PARAM_INPUT,
+                param.c_type,
+                param.sql_type,
+                param.column_size,
+                param.decimal_digits,
+                param.value_ptr(),
+                param.buffer_length(),
+                &param.indicator,
+            )
+        };
+        if !self.succeeded(result) {
+            return Err(InformixError::ParameterBindingError(
+                format!("Failed to bind parameter {}", param_num), self.get_diagnostics()
+            ));
+        }
+        self.params.borrow_mut().insert(param_num, param);
+        Ok(())
+    }
+
+    /// The SQL type, size and decimal digits of parameter `param_num`, via `SQLDescribeParam`.
+    fn describe_parameter(&self, param_num: u16) -> Option<(c_short, c_ulong, c_short)> {
+        let mut sql_type: c_short = 0;
+        let mut column_size: c_ulong = 0;
+        let mut decimal_digits: c_short = 0;
+        let mut nullable: c_short = 0;
+        let result = unsafe {
+            SQLDescribeParam(self.handle, param_num as c_ushort, &mut sql_type, &mut column_size,
+                &mut decimal_digits, &mut nullable)
+        };
+        self.succeeded(result).then_some((sql_type, column_size, decimal_digits))
     }
 
     pub fn execute(&self) -> Result<()> {
         let result = unsafe { SQLExecute(self.handle) };
-        if result != SQL_SUCCESS as c_short && result != SQL_SUCCESS_WITH_INFO as c_short {
-            Err(InformixError::SQLExecutionError(self.get_error_message()))
+        debug!(sql = %self.query, result, "executed prepared statement");
+        // Under ODBC 3 a searched UPDATE or DELETE that matches no rows returns SQL_NO_DATA
+        if result != SQL_NO_DATA && !self.succeeded(result) {
+            Err(InformixError::SQLExecutionError(format!("Failed to execute statement: {}", result), self.get_diagnostics()))
         } else {
             Ok(())
         }
     }
 
-    pub fn fetch(&self) -> Result<Option<Vec<String>>> {
+    /// Limits how long each execution or fetch may run, via
+    /// `SQL_ATTR_QUERY_TIMEOUT`. The driver counts whole seconds, so the
+    /// timeout is rounded up; `Duration::ZERO` removes the limit.
+    ///
+    /// A call that runs out of time fails with an error of kind `InformixErrorKind::Timeout`.
+    pub fn set_query_timeout(&self, timeout: Duration) -> Result<()> {
+        let result = unsafe {
+            SQLSetStmtAttr(self.handle, SQL_ATTR_QUERY_TIMEOUT, timeout_seconds(timeout) as *mut c_void, 0)
+        };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            Err(InformixError::SQLExecutionError("Failed to set query timeout".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Returns a handle that can cancel this statement from another thread.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle::new(Arc::clone(&self.cancel))
+    }
+
+    /// Closes the open cursor, discarding unread rows, so the statement can be
+    /// executed again. Does nothing if no cursor is open.
+    pub fn close_cursor(&self) -> Result<()> {
+        let result = unsafe { SQLFreeStmt(self.handle, SQL_CLOSE) };
+        if self.succeeded(result) {
+            Ok(())
+        } else {
+            Err(InformixError::SQLExecutionError("Failed to close cursor".to_string(), self.get_diagnostics()))
+        }
+    }
+
+    /// Returns the number of columns in the result set, via `SQLNumResultCols`.
+    pub fn column_count(&self) -> Result<u16> {
+        let mut count: c_short = 0;
+        let result = unsafe { SQLNumResultCols(self.handle, &mut count) };
+        if !self.succeeded(result) {
+            return Err(InformixError::DataFetchError(
+                "Failed to get column count".to_string(), self.get_diagnostics()
+            ));
+        }
+        Ok(count.max(0) as u16)
+    }
+
+    /// Describes every column of the result set.
+    ///
+    /// The descriptions are read once and cached for the lifetime of the statement.
+    pub fn columns(&self) -> Result<&[ColumnDesc]> {
+        if let Some(columns) = self.columns.get() {
+            return Ok(columns);
+        }
+        let columns = (1..=self.column_count()?)
+            .map(|column| self.describe_column(column))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.columns.get_or_init(|| columns))
+    }
+
+    /// Returns the 0-based index of the column called `name`.
+    ///
+    /// Informix stores unquoted identifiers in lowercase, so an exact match is
+    /// preferred and a case-insensitive match is used otherwise.
+    pub fn column_index(&self, name: &str) -> Result<usize> {
+        let columns = self.columns()?;
+        columns.iter().position(|c| c.name == name)
+            .or_else(|| columns.iter().position(|c| c.name.eq_ignore_ascii_case(name)))
+            .ok_or_else(|| InformixError::DataFetchError(format!("No column named {}", name), Vec::new()))
+    }
+
+    /// Describes column `column` (1-based) with `SQLDescribeCol` and `SQLColAttribute`.
+    pub fn describe_column(&self, column: u16) -> Result<ColumnDesc> {
+        let mut name = [0u8; 256];
+        let mut name_len: c_short = 0;
+        let mut sql_type: c_short = 0;
+        let mut size: c_ulong = 0;
+        let mut decimal_digits: c_short = 0;
+        let mut nullable: c_short = 0;
+        let result = unsafe {
+            SQLDescribeCol(
+                self.handle,
+                column as c_ushort,
+                name.as_mut_ptr() as *mut c_uchar,
+                name.len() as c_short,
+                &mut name_len,
+                &mut sql_type,
+                &mut size,
+                &mut decimal_digits,
+                &mut nullable,
+            )
+        };
+        if !self.succeeded(result) {
+            return Err(InformixError::DataFetchError(
+                format!("Failed to describe column {}", column), self.get_diagnostics()
+            ));
+        }
+        let name_len = (name_len.max(0) as usize).min(name.len() - 1);
+
+        Ok(ColumnDesc {
+            name: String::from_utf8_lossy(&name[..name_len]).into_owned(),
+            sql_type,
+            type_name: self.column_string_attribute(column, SQL_DESC_TYPE_NAME)?,
+            size: size as u64,
+            precision: self.column_numeric_attribute(column, SQL_DESC_PRECISION)? as i16,
+            scale: self.column_numeric_attribute(column, SQL_DESC_SCALE)? as i16,
+            nullable: match nullable {
+                SQL_NO_NULLS => Some(false),
+                SQL_NULLABLE => Some(true),
+                _ => None,
+            },
+        })
+    }
+
+    fn column_numeric_attribute(&self, column: u16, field: c_ushort) -> Result<c_long> {
+        let mut value: c_long = 0;
+        let result = unsafe {
+            SQLColAttribute(
+                self.handle,
+                column as c_ushort,
+                field,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                &mut value,
+            )
+        };
+        if !self.succeeded(result) {
+            return Err(InformixError::DataFetchError(
+                format!("Failed to get attribute {} of column {}", field, column), self.get_diagnostics()
+            ));
+        }
+        Ok(value)
+    }
+
+    fn column_string_attribute(&self, column: u16, field: c_ushort) -> Result<String> {
+        let mut buffer = [0u8; 256];
+        let mut len: c_short = 0;
+        let result = unsafe {
+            SQLColAttribute(
+                self.handle,
+                column as c_ushort,
+                field,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len() as c_short,
+                &mut len,
+                std::ptr::null_mut(),
+            )
+        };
+        if !self.succeeded(result) {
+            return Err(InformixError::DataFetchError(
+                format!("Failed to get attribute {} of column {}", field, column), self.get_diagnostics()
+            ));
+        }
+        let len = (len.max(0) as usize).min(buffer.len() - 1);
+        Ok(String::from_utf8_lossy(&buffer[..len]).into_owned())
+    }
+
+    /// Advances the cursor to the next row, returning `false` once the result set is exhausted.
+    fn fetch_next(&self) -> Result<bool> {
         let result = unsafe { SQLFetch(self.handle) };
         if result == SQL_NO_DATA.into() {
-            return Ok(None);
-        } else if result != SQL_SUCCESS.into() && result != SQL_SUCCESS_WITH_INFO.into() {
-            return Err(InformixError::DataFetchError(self.get_error_message()));
+            Ok(false)
+        } else if !self.succeeded(result as c_short) {
+            Err(InformixError::DataFetchError(format!("Failed to fetch row: {}", result), self.get_diagnostics()))
+        } else {
+            Ok(true)
         }
-    
-        let mut row = Vec::new();
-        for i in 1..=1000 { // We'll still try up to 1000 columns, but we'll break when we hit the end
-            let mut buffer = [0u8; 2048];
+    }
+
+    /// Fetches the next row and returns a typed accessor over its columns.
+    ///
+    /// Values are read from the driver when `Row::get` is called, using the
+    /// C type of the requested Rust type.
+    pub fn fetch_row(&self) -> Result<Option<Row<'_>>> {
+        if self.fetch_next()? {
+            Ok(Some(Row::new(self)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads column `column` (1-based) of the current row as `c_type`.
+    ///
+    /// Character and binary values longer than the first chunk are read with
+    /// repeated `SQLGetData` calls, sizing each further chunk from the length
+    /// indicator, so the full value is returned. Returns `None` when the value
+    /// is SQL NULL.
+    pub(crate) fn get_data(&self, column: u16, c_type: c_short) -> Result<Option<Vec<u8>>> {
+        // Character data is null-terminated inside every chunk
+        let terminator = if c_type == SQL_C_CHAR { 1 } else { 0 };
+        let mut data = Vec::new();
+        let mut chunk = vec![0u8; GET_DATA_CHUNK_SIZE];
+        let mut first = true;
+        loop {
             let mut indicator: c_long = 0;
             let result = unsafe {
                 SQLGetData(
                     self.handle,
-                    i as c_ushort,
-                    SQL_C_CHAR,
-                    buffer.as_mut_ptr() as *mut c_void,
-                    buffer.len() as c_long,
+                    column as c_ushort,
+                    c_type,
+                    chunk.as_mut_ptr() as *mut c_void,
+                    chunk.len() as c_long,
                     &mut indicator,
                 )
             };
-            if result == SQL_NO_DATA as c_short {
-                break; // We've reached the end of the columns
-            } else if result == SQL_SUCCESS as c_short || result == SQL_SUCCESS_WITH_INFO as c_short {
-                if indicator == SQL_NULL_DATA {
-                    row.push(String::from("NULL"));
-                } else {
-                    let s = unsafe { CStr::from_ptr(buffer.as_ptr() as *const c_char) }
-                        .to_string_lossy()
-                        .into_owned();
-                    row.push(s);
+            if result == SQL_NO_DATA {
+                if first {
+                    return Err(InformixError::DataFetchError(
+                        format!("Column {} has already been read for this row", column), Vec::new()
+                    ));
                 }
-            } else {
-                // If we get an error other than "Invalid descriptor index", return it
-                let error_message = self.get_error_message();
-                if !error_message.contains("Invalid descriptor index") {
+                break;
+            } else if result != SQL_SUCCESS && result != SQL_SUCCESS_WITH_INFO {
+                return Err(InformixError::DataFetchError(
+                    format!("GetData failed for column {}", column), self.get_diagnostics()
+                ));
+            }
+            if indicator == SQL_NULL_DATA {
+                return Ok(None);
+            }
+            first = false;
+
+            let capacity = chunk.len() - terminator;
+            let written = if indicator >= 0 { (indicator as usize).min(capacity) } else { capacity };
+            data.extend_from_slice(&chunk[..written]);
+            if result == SQL_SUCCESS {
+                break;
+            }
+
+            let has_more = indicator == SQL_NO_TOTAL || indicator as usize > capacity;
+            if !has_more {
+                // Info without pending data: only accept it if nothing was lost
+                let diagnostics = self.get_diagnostics();
+                if diagnostics.iter().any(|d| d.sqlstate == "01004" || d.sqlstate == "01S07") {
                     return Err(InformixError::DataFetchError(
-                        format!("GetData failed for column {}: {}", i, error_message)
+                        format!("Value of column {} was truncated", column), diagnostics
                     ));
                 }
-                // If we get "Invalid descriptor index", we've reached the end of the columns
+                self.warnings.extend(diagnostics);
                 break;
             }
+            if c_type != SQL_C_CHAR && c_type != SQL_C_BINARY {
+                return Err(InformixError::DataFetchError(
+                    format!("Value of column {} does not fit the requested type", column), self.get_diagnostics()
+                ));
+            }
+            let remaining = if indicator >= 0 {
+                indicator as usize - written
+            } else {
+                chunk.len() * 2
+            };
+            chunk = vec![0u8; remaining + terminator];
+        }
+        Ok(Some(data))
+    }
+
+    /// Fetches the next row as text, with SQL NULL values returned as `None`.
+    pub fn fetch(&self) -> Result<Option<Vec<Option<String>>>> {
+        if !self.fetch_next()? {
+            return Ok(None);
+        }
+    
+        let column_count = self.column_count()?;
+        let mut row = Vec::with_capacity(column_count as usize);
+        for column in 1..=column_count {
+            row.push(self.get_text(column)?);
         }
         Ok(Some(row))
     }
 
-    fn get_error_message(&self) -> String {
-        let mut state = [0i8; 6];
-        let mut native_error = 0i32;
-        let mut message = [0i8; 1024];
-        let mut out_len = 0i16;
-        
-        unsafe {
-            SQLGetDiagRec(
-                SQL_HANDLE_STMT,
-                self.handle,
-                1,
-                state.as_mut_ptr() as *mut c_char,
-                &mut native_error,
-                message.as_mut_ptr() as *mut c_char,
-                message.len() as c_short,
-                &mut out_len,
-            );
+    /// Fetches the next row as dynamically typed values, choosing each variant
+    /// from the described column type.
+    pub fn fetch_values(&self) -> Result<Option<Vec<Value>>> {
+        if !self.fetch_next()? {
+            return Ok(None);
         }
-        
-        let state = unsafe { CStr::from_ptr(state.as_ptr() as *const c_char) }.to_string_lossy();
-        let message = unsafe { CStr::from_ptr(message.as_ptr() as *const c_char) }.to_string_lossy();
-        
-        format!("SQLSTATE = {}, Native Error = {}, Message = {}", state, native_error, message)
+
+        let columns = self.columns()?;
+        let mut row = Vec::with_capacity(columns.len());
+        for (idx, column) in columns.iter().enumerate() {
+            row.push(Value::read(self, (idx + 1) as u16, column)?);
+        }
+        Ok(Some(row))
+    }
+
+    /// Reads column `column` (1-based) of the current row as text, or `None` if it is NULL.
+    pub(crate) fn get_text(&self, column: u16) -> Result<Option<String>> {
+        Ok(self.get_data(column, SQL_C_CHAR)?
+            .map(|raw| String::from_utf8_lossy(&raw).into_owned()))
+    }
+
+    fn get_diagnostics(&self) -> Vec<Diagnostic> {
+        get_diagnostics(SQL_HANDLE_STMT, self.handle)
+    }
+
+    /// Checks a CLI return code, recording the diagnostics of `SQL_SUCCESS_WITH_INFO` as warnings.
+    fn succeeded(&self, result: c_short) -> bool {
+        if result == SQL_SUCCESS_WITH_INFO {
+            self.warnings.record(SQL_HANDLE_STMT, self.handle);
+        }
+        result == SQL_SUCCESS || result == SQL_SUCCESS_WITH_INFO
+    }
+
+    /// Returns the warnings received so far by this statement, such as
+    /// truncated or rounded values.
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        self.warnings.get()
+    }
+
+    pub fn clear_warnings(&self) {
+        self.warnings.clear();
     }
 }
 
-impl Drop for Statement {
+// SAFETY: a statement only touches its own STMT handle, which the CLI allows on
+// any thread, and the buffers it owns; the borrowed `Connection` is never
+// accessed through it. Like `Connection` it is not `Sync`.
+unsafe impl Send for Statement<'_> {}
+
+impl Drop for Statement<'_> {
     fn drop(&mut self) {
-        unsafe {
-            SQLFreeHandle(3, self.handle);
-        }
+        self.cancel.release(|handle| unsafe {
+            SQLFreeHandle(SQL_HANDLE_STMT, handle);
+        });
     }
 }
 
+/// Conversion of a Rust value into a parameter for `SQLBindParameter`.
+///
+/// The returned `SqlParam` owns the buffer and length indicator handed to the
+/// driver; `Statement` keeps it alive until the parameter is re-bound or the
+/// statement is dropped.
 pub trait ToSql {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()>;
+    fn to_sql(&self) -> Result<SqlParam>;
+
+    /// A NULL of this type, bound for `None`.
+    ///
+    /// The default is an untyped `SqlParam::null()`; types with a fixed SQL
+    /// type return `SqlParam::into_null` of a value instead.
+    fn null_sql() -> Result<SqlParam> where Self: Sized {
+        Ok(SqlParam::null())
+    }
 }
 
 impl ToSql for i32 {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()> {
-        let result = unsafe {
-            SQLBindParameter(
-                stmt,
-                param_num,
-                SQL_PARAM_INPUT,
-                SQL_C_LONG,
-                SQL_INTEGER,
-                0,
-                0,
-                self as *const i32 as *const c_void,
-                0,
-                std::ptr::null(),
-            )
-        };
-        if result == SQL_SUCCESS as c_short || result == SQL_SUCCESS_WITH_INFO as c_short {
-            Ok(())
-        } else {
-            Err(InformixError::ParameterBindingError(format!("Failed to bind i32 parameter: {}", result)))
-        }
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(*self, SQL_C_LONG, SQL_INTEGER, 0, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
     }
 }
-impl ToSql for &str {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()> {
-        let c_str = CString::new(*self)
-            .map_err(|e| InformixError::ParameterBindingError(format!("Failed to create CString: {}", e)))?;
-        
-        let result = unsafe {
-            SQLBindParameter(
-                stmt,
-                param_num as c_ushort,
-                SQL_PARAM_INPUT,
-                SQL_C_CHAR,
-                SQL_VARCHAR,
-                self.len() as c_ulong,
-                0,  // decimal digits
-                c_str.as_ptr() as *const c_void,
-                self.len() as c_long,
-                &(self.len() as c_long) as *const c_long
-            )
-        };
 
-        if result == SQL_SUCCESS || result == SQL_SUCCESS_WITH_INFO {
-            Ok(())
-        } else {
-            Err(InformixError::ParameterBindingError(format!("Failed to bind string parameter: SQLBindParameter returned {}", result)))
-        }
+impl ToSql for &str {
+    fn to_sql(&self) -> Result<SqlParam> {
+        (**self).to_sql()
     }
 }
+
 impl ToSql for str {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()> {
-        let c_str = CString::new(self)
-            .map_err(|e| InformixError::ParameterBindingError(format!("Failed to create CString: {}", e)))?;
-        let result = unsafe {
-            SQLBindParameter(
-                stmt,
-                param_num,
-                SQL_PARAM_INPUT,
-                SQL_C_CHAR,
-                SQL_VARCHAR,
-                self.len() as c_ulong,
-                0,
-                c_str.as_ptr() as *const c_void,
-                0,
-                &(self.len() as c_long) as *const c_long,
-            )
-        };
-        if result == SQL_SUCCESS as c_short || result == SQL_SUCCESS_WITH_INFO as c_short {
-            Ok(())
-        } else {
-            Err(InformixError::ParameterBindingError(format!("Failed to bind string parameter: {}", result)))
-        }
+    fn to_sql(&self) -> Result<SqlParam> {
+        SqlParam::text(self)
     }
 }
 
 impl ToSql for String {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()> {
-        self.as_str().bind_parameter(stmt, param_num)
+    fn to_sql(&self) -> Result<SqlParam> {
+        self.as_str().to_sql()
+    }
+}
+
+impl<T: ToSql> ToSql for Option<T> {
+    fn to_sql(&self) -> Result<SqlParam> {
+        match self {
+            Some(value) => value.to_sql(),
+            None => T::null_sql(),
+        }
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        T::null_sql()
+    }
+}
+
+impl ToSql for i16 {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(*self, SQL_C_SHORT, SQL_SMALLINT, 0, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+impl ToSql for i64 {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(*self, SQL_C_SBIGINT, SQL_BIGINT, 0, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+impl ToSql for f32 {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(*self, SQL_C_FLOAT, SQL_REAL, 0, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+impl ToSql for f64 {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(*self, SQL_C_DOUBLE, SQL_DOUBLE, 0, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+impl ToSql for bool {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::fixed(u8::from(*self), SQL_C_BIT, SQL_BIT, 1, 0))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+impl ToSql for Vec<u8> {
+    fn to_sql(&self) -> Result<SqlParam> {
+        Ok(SqlParam::binary(self))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Ok(SqlParam::binary(&[0]).into_null())
     }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 struct SQL_DATE_STRUCT {
     year: c_short,
     month: c_ushort,
@@ -439,40 +1189,80 @@ struct SQL_DATE_STRUCT {
 }
 
 impl ToSql for NaiveDate {
-    fn bind_parameter(&self, stmt: *mut c_void, param_num: u16) -> Result<()> {
-        // Create a SQL_DATE_STRUCT
+    fn to_sql(&self) -> Result<SqlParam> {
         let date_struct = SQL_DATE_STRUCT {
             year: self.year() as c_short,
             month: self.month() as c_ushort,
             day: self.day() as c_ushort,
         };
+        // size of YYYY-MM-DD
+        Ok(SqlParam::fixed(date_struct, SQL_C_TYPE_DATE, SQL_TYPE_DATE, 10, 0))
+    }
 
-        let result = unsafe {
-            SQLBindParameter(
-                stmt,
-                param_num,
-                SQL_PARAM_INPUT,
-                SQL_TYPE_DATE,
-                SQL_TYPE_DATE,
-                10,  // size of YYYY-MM-DD
-                0,   // decimal digits
-                &date_struct as *const SQL_DATE_STRUCT as *const c_void,
-                mem::size_of::<SQL_DATE_STRUCT>() as c_long,
-                std::ptr::null(),
-            )
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SQL_TIME_STRUCT {
+    hour: c_ushort,
+    minute: c_ushort,
+    second: c_ushort,
+}
+
+impl ToSql for NaiveTime {
+    fn to_sql(&self) -> Result<SqlParam> {
+        let time_struct = SQL_TIME_STRUCT {
+            hour: self.hour() as c_ushort,
+            minute: self.minute() as c_ushort,
+            second: self.second() as c_ushort,
         };
+        Ok(SqlParam::fixed(time_struct, SQL_C_TYPE_TIME, SQL_TYPE_TIME, 8, 0))
+    }
 
-        if result == SQL_SUCCESS as c_short || result == SQL_SUCCESS_WITH_INFO as c_short {
-            Ok(())
-        } else {
-            Err(InformixError::ParameterBindingError(format!("Failed to bind date parameter: SQLBindParameter returned {}", result)))
-        }
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SQL_TIMESTAMP_STRUCT {
+    year: c_short,
+    month: c_ushort,
+    day: c_ushort,
+    hour: c_ushort,
+    minute: c_ushort,
+    second: c_ushort,
+    fraction: u32,
+}
+
+impl ToSql for NaiveDateTime {
+    fn to_sql(&self) -> Result<SqlParam> {
+        let timestamp_struct = SQL_TIMESTAMP_STRUCT {
+            year: self.year() as c_short,
+            month: self.month() as c_ushort,
+            day: self.day() as c_ushort,
+            hour: self.hour() as c_ushort,
+            minute: self.minute() as c_ushort,
+            second: self.second() as c_ushort,
+            // Informix keeps at most FRACTION(5), i.e. 10 microsecond precision
+            fraction: self.nanosecond() / 10_000 * 10_000,
+        };
+        // size of YYYY-MM-DD hh:mm:ss.fffff
+        Ok(SqlParam::fixed(timestamp_struct, SQL_C_TYPE_TIMESTAMP, SQL_TYPE_TIMESTAMP, 25, 5))
+    }
+
+    fn null_sql() -> Result<SqlParam> {
+        Self::default().to_sql().map(SqlParam::into_null)
     }
 }
 
 // Higher-level abstractions
 pub struct Cursor<'a> {
-    pub stmt: Statement,
+    pub stmt: Statement<'a>,
     pub conn: &'a Connection,
 }
 
@@ -485,16 +1275,20 @@ impl<'a> Cursor<'a> {
     pub fn execute_with_params(&mut self, sql: &str, params: &[&dyn ToSql]) -> Result<()> {
         self.stmt = self.conn.prepare(sql)?;
         for (i, param) in params.iter().enumerate() {
-            param.bind_parameter(self.stmt.handle, (i + 1) as u16)?;
+            self.stmt.bind_parameter((i + 1) as u16, *param)?;
         }
         self.stmt.execute()
     }
 
-    pub fn fetchone(&self) -> Result<Option<Vec<String>>> {
+    pub fn fetchone(&self) -> Result<Option<Vec<Option<String>>>> {
         self.stmt.fetch()
     }
 
-    pub fn fetchall(&self) -> Result<Vec<Vec<String>>> {
+    pub fn fetch_row(&self) -> Result<Option<Row<'_>>> {
+        self.stmt.fetch_row()
+    }
+
+    pub fn fetchall(&self) -> Result<Vec<Vec<Option<String>>>> {
         let mut results = Vec::new();
         while let Some(row) = self.stmt.fetch()? {
             results.push(row);
@@ -502,3 +1296,39 @@ impl<'a> Cursor<'a> {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_binds_as_a_null_of_its_type() {
+        let param = None::<i64>.to_sql().unwrap();
+        assert_eq!((param.c_type, param.sql_type, param.indicator), (SQL_C_SBIGINT, SQL_BIGINT, SQL_NULL_DATA));
+        assert!(!param.is_untyped());
+
+        let param = None::<NaiveDate>.to_sql().unwrap();
+        assert_eq!((param.sql_type, param.column_size, param.indicator), (SQL_TYPE_DATE, 10, SQL_NULL_DATA));
+
+        let param = Some(None::<i32>).to_sql().unwrap();
+        assert_eq!((param.sql_type, param.indicator), (SQL_INTEGER, SQL_NULL_DATA));
+    }
+
+    #[test]
+    fn untyped_nulls_are_left_to_the_driver() {
+        assert!(None::<String>.to_sql().unwrap().is_untyped());
+        let param = Value::Null.to_sql().unwrap();
+        assert!(param.is_untyped());
+        assert_eq!(param.indicator, SQL_NULL_DATA);
+        assert!(!Value::Integer(1).to_sql().unwrap().is_untyped());
+    }
+
+    #[test]
+    fn timeout_seconds_rounds_up() {
+        assert_eq!(timeout_seconds(Duration::ZERO), 0);
+        assert_eq!(timeout_seconds(Duration::from_millis(1)), 1);
+        assert_eq!(timeout_seconds(Duration::from_secs(5)), 5);
+        assert_eq!(timeout_seconds(Duration::from_millis(5001)), 6);
+        assert_eq!(timeout_seconds(Duration::MAX), c_ulong::MAX);
+    }
+}
@@ -0,0 +1,119 @@
+// Internal logging macros: they forward to `tracing` when the feature is
+// enabled and expand to nothing otherwise, so arguments are never evaluated.
+
+#[cfg(feature = "tracing")]
+macro_rules! debug {
+    ($($arg:tt)*) => { tracing::debug!(target: "informix_rust", $($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! warn {
+    ($($arg:tt)*) => { tracing::warn!(target: "informix_rust", $($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! warn {
+    ($($arg:tt)*) => {};
+}
+
+// Connection string keys whose values are never logged
+const SECRET_KEYS: &[&str] = &["UID", "USER", "USERID", "PWD", "PASSWORD", "PASSWD", "AUTHENTICATION"];
+const SECRET_FRAGMENTS: &[&str] = &["PASSWORD", "PWD", "SECRET", "TOKEN", "KEY"];
+
+/// Masks credentials in a `KEY=value;...` connection string so it can be logged.
+///
+/// UID/PWD and any key that looks like a password, secret, token or key are
+/// replaced with `****`; everything else is kept as is. Brace-quoted values
+/// such as `PWD={a;b}` are masked whole.
+pub fn redact_connection_string(conn_string: &str) -> String {
+    split_pairs(conn_string)
+        .into_iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if is_secret_key(key) => format!("{}=****", key),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Splits a connection string on the `;` separators that are not inside an
+/// ODBC `{...}` value, where `}}` stands for a literal `}`. An unterminated
+/// brace runs to the end of the string.
+fn split_pairs(conn_string: &str) -> Vec<&str> {
+    let bytes = conn_string.as_bytes();
+    let mut pairs = Vec::new();
+    let mut start = 0;
+    let mut in_value = false;
+    let mut in_braces = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'}' if in_braces => {
+                if bytes.get(i + 1) == Some(&b'}') {
+                    i += 1;
+                } else {
+                    in_braces = false;
+                }
+            }
+            _ if in_braces => {}
+            b'=' if !in_value => {
+                in_value = true;
+                let value = &conn_string[i + 1..];
+                in_braces = value.trim_start().starts_with('{');
+                if in_braces {
+                    i += value.find('{').unwrap_or(0) + 1;
+                }
+            }
+            b';' => {
+                pairs.push(&conn_string[start..i]);
+                start = i + 1;
+                in_value = false;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    pairs.push(&conn_string[start..]);
+    pairs
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.trim().to_ascii_uppercase();
+    SECRET_KEYS.contains(&key.as_str()) || SECRET_FRAGMENTS.iter().any(|fragment| key.contains(fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_credentials() {
+        assert_eq!(
+            redact_connection_string("DSN=stores;UID=informix;PWD=secret;"),
+            "DSN=stores;UID=****;PWD=****;"
+        );
+        assert_eq!(redact_connection_string("SERVER=ol;ApiKey=abc"), "SERVER=ol;ApiKey=****");
+    }
+
+    #[test]
+    fn masks_brace_quoted_values_whole() {
+        assert_eq!(redact_connection_string("PWD={ab;cd}"), "PWD=****");
+        assert_eq!(redact_connection_string("DSN=x;PWD={ab;cd};UID=u"), "DSN=x;PWD=****;UID=****");
+        assert_eq!(redact_connection_string("PWD = {a}};b;c};DSN=x"), "PWD =****;DSN=x");
+    }
+
+    #[test]
+    fn keeps_brace_quoted_values_of_other_keys() {
+        assert_eq!(redact_connection_string("DATABASE={my;db};PWD=x"), "DATABASE={my;db};PWD=****");
+    }
+
+    #[test]
+    fn masks_unterminated_brace_to_the_end() {
+        assert_eq!(redact_connection_string("PWD={abc;DSN=x"), "PWD=****");
+    }
+}
@@ -0,0 +1,155 @@
+use std::env;
+use std::time::Duration;
+use crate::errors::{Diagnostic, InformixError};
+
+// What the client library uses when INFORMIXCONTIME is not set
+const DEFAULT_CONNECT_TIME: Duration = Duration::from_secs(60);
+
+/// Timeouts applied by `Connection::connect_with_options`.
+///
+/// Every setting is optional; unset ones keep the driver's defaults. The
+/// login and connection timeouts are rounded up to whole seconds, and
+/// `Some(Duration::ZERO)` means no timeout.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectOptions {
+    /// How long to wait for a login to complete, via `SQL_ATTR_LOGIN_TIMEOUT`.
+    pub login_timeout: Option<Duration>,
+    /// How long any request on the connection may wait for the server, via
+    /// `SQL_ATTR_CONNECTION_TIMEOUT`.
+    pub connection_timeout: Option<Duration>,
+    /// Total time the client library spends trying to reach the server, in
+    /// whole seconds. Sent as the `INFORMIXCONTIME` connection-string
+    /// attribute; when unset, the environment variable of that name applies.
+    pub connect_time: Option<Duration>,
+    /// Additional connection attempts made within `connect_time`. Sent as the
+    /// `INFORMIXCONRETRY` connection-string attribute.
+    pub connect_retry: Option<u32>,
+}
+
+impl ConnectOptions {
+    /// `conn_string` with the `connect_time` and `connect_retry` attributes appended.
+    pub(crate) fn connection_string(&self, conn_string: &str) -> String {
+        let mut conn_string = conn_string.to_string();
+        let attributes = [
+            self.connect_time.map(|time| ("INFORMIXCONTIME", crate::timeout_seconds(time).to_string())),
+            self.connect_retry.map(|retry| ("INFORMIXCONRETRY", retry.to_string())),
+        ];
+        for (name, value) in attributes.into_iter().flatten() {
+            if !conn_string.is_empty() && !conn_string.ends_with(';') {
+                conn_string.push(';');
+            }
+            conn_string.push_str(&format!("{}={};", name, value));
+        }
+        conn_string
+    }
+
+    /// The `INFORMIXCONTIME` a connect with these options runs under.
+    pub(crate) fn connect_time(&self) -> Option<Duration> {
+        match self.connect_time {
+            Some(time) => Some(Duration::from_secs(crate::timeout_seconds(time) as u64)).filter(|time| !time.is_zero()),
+            None => env_connect_time(),
+        }
+    }
+}
+
+/// The `INFORMIXCONTIME` the client library uses, read from the process
+/// environment; `None` when it is disabled.
+pub(crate) fn env_connect_time() -> Option<Duration> {
+    parse_connect_time(env::var("INFORMIXCONTIME").ok().as_deref())
+}
+
+fn parse_connect_time(value: Option<&str>) -> Option<Duration> {
+    match value {
+        Some(value) => value.trim().parse().ok().filter(|&seconds| seconds > 0).map(Duration::from_secs),
+        None => Some(DEFAULT_CONNECT_TIME),
+    }
+}
+
+/// The error for a connect that failed after `elapsed` with `diagnostics`.
+///
+/// A login or connection timeout is reported with SQLSTATE HYT00 or HYT01
+/// and becomes `InformixError::ConnectTimeout`, as does running out of
+/// `connect_time`; see `expired_connect_time`.
+pub(crate) fn connect_error(
+    message: String,
+    diagnostics: Vec<Diagnostic>,
+    elapsed: Duration,
+    connect_time: Option<Duration>,
+) -> InformixError {
+    if diagnostics.iter().any(|diagnostic| matches!(diagnostic.sqlstate.as_str(), "HYT00" | "HYT01")) {
+        InformixError::ConnectTimeout("login timeout expired".to_string(), diagnostics)
+    } else if let Some(connect_time) = expired_connect_time(connect_time, elapsed, &diagnostics) {
+        InformixError::ConnectTimeout(format!("gave up after INFORMIXCONTIME ({:?})", connect_time), diagnostics)
+    } else {
+        InformixError::ConnectionError(message, diagnostics)
+    }
+}
+
+/// The `connect_time` that ran out, if a connect that failed after `elapsed`
+/// with `diagnostics` gave up because of it.
+///
+/// This is a best-effort guess: the client library reports it as -908, like
+/// a server that refused the connection, so only the time the connect took
+/// tells the two apart. A refusal that arrives after `connect_time` has
+/// passed is reported as a timeout too.
+pub(crate) fn expired_connect_time(
+    connect_time: Option<Duration>,
+    elapsed: Duration,
+    diagnostics: &[Diagnostic],
+) -> Option<Duration> {
+    let connect_time = connect_time?;
+    let refused = diagnostics.iter().any(|diagnostic| diagnostic.native_code == -908);
+    (refused && elapsed >= connect_time).then_some(connect_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::InformixErrorKind;
+
+    fn diagnostic(sqlstate: &str, native_code: i32) -> Diagnostic {
+        Diagnostic::new(sqlstate.to_string(), native_code, String::new(), None)
+    }
+
+    #[test]
+    fn connect_attributes_appended() {
+        let options = ConnectOptions {
+            connect_time: Some(Duration::from_millis(2500)),
+            connect_retry: Some(2),
+            ..ConnectOptions::default()
+        };
+        assert_eq!(options.connection_string("DSN=stores"), "DSN=stores;INFORMIXCONTIME=3;INFORMIXCONRETRY=2;");
+        assert_eq!(options.connection_string("DSN=stores;"), "DSN=stores;INFORMIXCONTIME=3;INFORMIXCONRETRY=2;");
+        assert_eq!(ConnectOptions::default().connection_string("DSN=stores"), "DSN=stores");
+        assert_eq!(options.connect_time(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn connect_time_from_environment() {
+        assert_eq!(parse_connect_time(None), Some(DEFAULT_CONNECT_TIME));
+        assert_eq!(parse_connect_time(Some(" 15 ")), Some(Duration::from_secs(15)));
+        assert_eq!(parse_connect_time(Some("0")), None);
+        assert_eq!(parse_connect_time(Some("soon")), None);
+    }
+
+    #[test]
+    fn refusal_after_connect_time_is_expired() {
+        let refused = [diagnostic("08004", -908)];
+        let connect_time = Some(Duration::from_secs(10));
+        assert_eq!(expired_connect_time(connect_time, Duration::from_secs(10), &refused), connect_time);
+        assert_eq!(expired_connect_time(connect_time, Duration::from_secs(9), &refused), None);
+        assert_eq!(expired_connect_time(None, Duration::from_secs(600), &refused), None);
+        assert_eq!(expired_connect_time(connect_time, Duration::from_secs(10), &[diagnostic("28000", -951)]), None);
+    }
+
+    #[test]
+    fn login_timeout_is_a_connect_timeout() {
+        let error = connect_error("result = -1".to_string(), vec![diagnostic("08004", -908), diagnostic("HYT00", -1)], Duration::ZERO, None);
+        assert!(matches!(error, InformixError::ConnectTimeout(..)));
+        assert_eq!(error.kind(), InformixErrorKind::Timeout);
+
+        let error = connect_error("result = -1".to_string(), vec![diagnostic("08004", -908)], Duration::ZERO, None);
+        assert!(matches!(error, InformixError::ConnectionError(..)));
+        assert_eq!(error.kind(), InformixErrorKind::ConnectionFailed);
+    }
+}
@@ -0,0 +1,153 @@
+use std::mem;
+use std::os::raw::{c_long, c_short, c_ulong, c_void};
+use crate::errors::Result;
+use crate::{to_cstring, SQL_C_BINARY, SQL_C_CHAR, SQL_LONGVARBINARY, SQL_NULL_DATA, SQL_VARCHAR};
+
+// Storage for fixed-size C types; large and aligned enough for SQL_TIMESTAMP_STRUCT
+type FixedBuffer = [u64; 2];
+
+enum ParamBuffer {
+    Null,
+    Fixed(FixedBuffer, usize),
+    Bytes(Vec<u8>),
+}
+
+/// A parameter value converted for `SQLBindParameter`.
+///
+/// It owns both the value buffer and the length indicator, whose addresses are
+/// read by the driver at execution time.
+pub struct SqlParam {
+    pub(crate) c_type: c_short,
+    pub(crate) sql_type: c_short,
+    pub(crate) column_size: c_ulong,
+    pub(crate) decimal_digits: c_short,
+    pub(crate) indicator: c_long,
+    buffer: ParamBuffer,
+}
+
+impl SqlParam {
+    /// A SQL NULL of no particular type.
+    ///
+    /// `Statement::bind_parameter` binds it with the type the driver describes
+    /// for the parameter, or as VARCHAR if the driver cannot describe it.
+    pub fn null() -> Self {
+        SqlParam {
+            c_type: SQL_C_CHAR,
+            sql_type: SQL_VARCHAR,
+            column_size: 1,
+            decimal_digits: 0,
+            indicator: SQL_NULL_DATA,
+            buffer: ParamBuffer::Null,
+        }
+    }
+
+    /// The same parameter with a NULL value, still bound with its C and SQL types.
+    pub fn into_null(mut self) -> Self {
+        self.indicator = SQL_NULL_DATA;
+        self
+    }
+
+    /// Whether this is a `null()` whose type is left to the driver.
+    pub(crate) fn is_untyped(&self) -> bool {
+        matches!(self.buffer, ParamBuffer::Null)
+    }
+
+    /// A character parameter, bound as VARCHAR.
+    pub fn text(value: &str) -> Result<Self> {
+        let c_str = to_cstring(value, "string parameter")?;
+        Ok(SqlParam {
+            c_type: SQL_C_CHAR,
+            sql_type: SQL_VARCHAR,
+            column_size: value.len() as c_ulong,
+            decimal_digits: 0,
+            indicator: value.len() as c_long,
+            buffer: ParamBuffer::Bytes(c_str.into_bytes_with_nul()),
+        })
+    }
+
+    /// A binary parameter, bound as BYTE.
+    pub fn binary(value: &[u8]) -> Self {
+        SqlParam {
+            c_type: SQL_C_BINARY,
+            sql_type: SQL_LONGVARBINARY,
+            column_size: value.len() as c_ulong,
+            decimal_digits: 0,
+            indicator: value.len() as c_long,
+            buffer: ParamBuffer::Bytes(value.to_vec()),
+        }
+    }
+
+    /// A fixed-size parameter whose memory layout matches `c_type`.
+    ///
+    /// `T` must be a primitive or a `#[repr(C)]` struct without padding.
+    pub(crate) fn fixed<T: Copy>(value: T, c_type: c_short, sql_type: c_short,
+        column_size: c_ulong, decimal_digits: c_short) -> Self {
+        let size = mem::size_of::<T>();
+        assert!(size <= mem::size_of::<FixedBuffer>() && mem::align_of::<T>() <= mem::align_of::<FixedBuffer>());
+        let mut buffer: FixedBuffer = [0; 2];
+        unsafe { std::ptr::write(buffer.as_mut_ptr() as *mut T, value) };
+        SqlParam {
+            c_type,
+            sql_type,
+            column_size,
+            decimal_digits,
+            indicator: size as c_long,
+            buffer: ParamBuffer::Fixed(buffer, size),
+        }
+    }
+
+    pub(crate) fn value_ptr(&self) -> *const c_void {
+        match &self.buffer {
+            ParamBuffer::Null => std::ptr::null(),
+            ParamBuffer::Fixed(buffer, _) => buffer.as_ptr() as *const c_void,
+            ParamBuffer::Bytes(bytes) => bytes.as_ptr() as *const c_void,
+        }
+    }
+
+    pub(crate) fn buffer_length(&self) -> c_long {
+        match &self.buffer {
+            ParamBuffer::Null => 0,
+            ParamBuffer::Fixed(_, size) => *size as c_long,
+            ParamBuffer::Bytes(bytes) => bytes.len() as c_long,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SQL_C_LONG, SQL_INTEGER};
+
+    fn bytes(param: &SqlParam) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(param.value_ptr() as *const u8, param.buffer_length() as usize) }
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Pair {
+        first: u16,
+        second: u16,
+        third: u32,
+    }
+
+    #[test]
+    fn fixed_copies_the_value_layout() {
+        let param = SqlParam::fixed(-2i32, SQL_C_LONG, SQL_INTEGER, 0, 0);
+        assert_eq!(bytes(&param), (-2i32).to_ne_bytes());
+        assert_eq!(param.indicator, 4);
+
+        let param = SqlParam::fixed(Pair { first: 1, second: 2, third: 3 }, SQL_C_BINARY, SQL_LONGVARBINARY, 8, 0);
+        let expected = [1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat();
+        assert_eq!(bytes(&param)[..4], expected[..]);
+        assert_eq!(bytes(&param)[4..], 3u32.to_ne_bytes());
+        assert_eq!((param.buffer_length(), param.indicator), (8, 8));
+    }
+
+    #[test]
+    fn text_is_nul_terminated() {
+        let param = SqlParam::text("abc").unwrap();
+        assert_eq!(bytes(&param), b"abc\0");
+        assert_eq!((param.column_size, param.indicator), (3, 3));
+        assert!(SqlParam::text("a\0b").is_err());
+    }
+}
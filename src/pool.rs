@@ -0,0 +1,548 @@
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use crate::errors::{InformixError, Result};
+use crate::{Connection, IsolationLevel, LockMode};
+
+/// Creates, checks and resets the connections held by a `Pool`.
+///
+/// `InformixManager` is the implementation for real Informix connections;
+/// other implementations can stand in for the driver, e.g. in tests.
+pub trait ConnectionManager: Send + Sync + 'static {
+    type Connection: Send + 'static;
+
+    /// Opens a new connection.
+    fn connect(&self) -> Result<Self::Connection>;
+
+    /// Checks that a connection is still usable before it is handed out.
+    fn is_valid(&self, conn: &mut Self::Connection) -> Result<()>;
+
+    /// Returns a connection to a clean session state when it comes back to the pool.
+    fn reset(&self, conn: &mut Self::Connection) -> Result<()>;
+}
+
+/// Manages `Connection`s opened from a connection string.
+///
+/// Every connection starts with, and is reset to, the configured isolation
+/// level and lock mode: `CommittedRead` and `NotWait` unless changed.
+#[derive(Debug, Clone)]
+pub struct InformixManager {
+    conn_string: String,
+    health_query: String,
+    isolation: IsolationLevel,
+    lock_mode: LockMode,
+}
+
+impl InformixManager {
+    pub fn new(conn_string: &str) -> Self {
+        InformixManager {
+            conn_string: conn_string.to_string(),
+            health_query: "SELECT 1 FROM sysmaster:sysdual".to_string(),
+            isolation: IsolationLevel::CommittedRead,
+            lock_mode: LockMode::NotWait,
+        }
+    }
+
+    /// Replaces the query run by `is_valid`.
+    pub fn health_query(mut self, query: &str) -> Self {
+        self.health_query = query.to_string();
+        self
+    }
+
+    /// Sets the isolation level connections start with and are reset to.
+    pub fn isolation(mut self, level: IsolationLevel) -> Self {
+        self.isolation = level;
+        self
+    }
+
+    /// Sets the lock mode connections start with and are reset to.
+    pub fn lock_mode(mut self, mode: LockMode) -> Self {
+        self.lock_mode = mode;
+        self
+    }
+
+    fn restore_session(&self, conn: &Connection) -> Result<()> {
+        conn.set_isolation(self.isolation)?;
+        conn.set_lock_mode(self.lock_mode)
+    }
+}
+
+impl ConnectionManager for InformixManager {
+    type Connection = Connection;
+
+    fn connect(&self) -> Result<Connection> {
+        let conn = Connection::new()?;
+        conn.connect_with_string(&self.conn_string)?;
+        self.restore_session(&conn)?;
+        Ok(conn)
+    }
+
+    fn is_valid(&self, conn: &mut Connection) -> Result<()> {
+        conn.execute(&self.health_query).map(drop)
+    }
+
+    /// Rolls back any open transaction, turns autocommit back on, restores
+    /// the isolation level and lock mode, and clears recorded warnings.
+    fn reset(&self, conn: &mut Connection) -> Result<()> {
+        if !conn.autocommit()? {
+            conn.rollback()?;
+            conn.set_autocommit(true)?;
+        }
+        self.restore_session(conn)?;
+        conn.clear_warnings();
+        Ok(())
+    }
+}
+
+/// Settings for a `Pool`.
+#[derive(Debug, Clone)]
+pub struct PoolOptions {
+    /// Connections opened when the pool is created. Idle timeouts never close
+    /// connections below this count, but connections closed for a failed
+    /// check or at `max_lifetime` are only replaced on demand.
+    pub min_size: usize,
+    pub max_size: usize,
+    /// How long `Pool::get` waits for a connection before failing.
+    pub acquire_timeout: Duration,
+    /// Idle connections above `min_size` are closed after this long.
+    pub idle_timeout: Option<Duration>,
+    /// Connections are closed once they are this old, whether idle or not.
+    pub max_lifetime: Option<Duration>,
+    /// Run `ConnectionManager::is_valid` before handing out an idle connection.
+    pub test_on_checkout: bool,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        PoolOptions {
+            min_size: 0,
+            max_size: 10,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            max_lifetime: Some(Duration::from_secs(1800)),
+            test_on_checkout: true,
+        }
+    }
+}
+
+struct Idle<C> {
+    conn: C,
+    created: Instant,
+    returned: Instant,
+}
+
+struct State<C> {
+    idle: VecDeque<Idle<C>>,
+    /// Open connections, idle or checked out, plus those being opened.
+    total: usize,
+}
+
+struct Shared<M: ConnectionManager> {
+    manager: M,
+    options: PoolOptions,
+    state: Mutex<State<M::Connection>>,
+    available: Condvar,
+}
+
+impl<M: ConnectionManager> Shared<M> {
+    fn lock(&self) -> MutexGuard<'_, State<M::Connection>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn expired(&self, created: Instant, now: Instant) -> bool {
+        self.options.max_lifetime.is_some_and(|lifetime| now.saturating_duration_since(created) >= lifetime)
+    }
+
+    /// Removes every idle connection past its idle timeout or lifetime, so
+    /// the caller can close them after releasing the lock.
+    fn take_expired(&self, state: &mut State<M::Connection>, now: Instant) -> Vec<M::Connection> {
+        let mut expired = Vec::new();
+        let mut i = 0;
+        // The queue is ordered by return time, so the longest idle are checked first
+        while i < state.idle.len() {
+            let idle = &state.idle[i];
+            let idle_expired = state.total > self.options.min_size
+                && self.options.idle_timeout.is_some_and(|timeout| now.saturating_duration_since(idle.returned) >= timeout);
+            if idle_expired || self.expired(idle.created, now) {
+                if let Some(idle) = state.idle.remove(i) {
+                    expired.push(idle.conn);
+                }
+                state.total -= 1;
+            } else {
+                i += 1;
+            }
+        }
+        expired
+    }
+
+    /// Forgets a connection that has been closed and wakes one waiter.
+    fn release_slot(&self) {
+        self.lock().total -= 1;
+        self.available.notify_one();
+    }
+}
+
+/// A thread-safe pool of connections.
+///
+/// Cloning a `Pool` is cheap and yields a handle to the same pool.
+pub struct Pool<M: ConnectionManager> {
+    shared: Arc<Shared<M>>,
+}
+
+impl<M: ConnectionManager> Clone for Pool<M> {
+    fn clone(&self) -> Self {
+        Pool { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<M: ConnectionManager> Pool<M> {
+    pub fn new(manager: M) -> Result<Self> {
+        Self::with_options(manager, PoolOptions::default())
+    }
+
+    /// Creates a pool and opens `min_size` connections up front.
+    pub fn with_options(manager: M, options: PoolOptions) -> Result<Self> {
+        if options.max_size == 0 || options.min_size > options.max_size {
+            return Err(InformixError::InvalidInput(format!(
+                "pool size must satisfy 0 <= min_size <= max_size and max_size > 0, got {}..={}",
+                options.min_size, options.max_size
+            )));
+        }
+        let mut idle = VecDeque::with_capacity(options.max_size);
+        for _ in 0..options.min_size {
+            let now = Instant::now();
+            idle.push_back(Idle { conn: manager.connect()?, created: now, returned: now });
+        }
+        let total = idle.len();
+        Ok(Pool {
+            shared: Arc::new(Shared {
+                manager,
+                options,
+                state: Mutex::new(State { idle, total }),
+                available: Condvar::new(),
+            }),
+        })
+    }
+
+    /// Checks out a connection, opening one if the pool is below `max_size`.
+    ///
+    /// Waits up to `acquire_timeout` for a connection to be returned and fails
+    /// with `InformixError::PoolTimeout` otherwise.
+    pub fn get(&self) -> Result<PooledConnection<M>> {
+        let shared = &self.shared;
+        let deadline = Instant::now() + shared.options.acquire_timeout;
+        let mut state = shared.lock();
+        loop {
+            let now = Instant::now();
+            let expired = shared.take_expired(&mut state, now);
+            if !expired.is_empty() {
+                // Disconnect without holding the lock
+                drop(state);
+                drop(expired);
+                shared.available.notify_all();
+                state = shared.lock();
+                continue;
+            }
+            // Most recently returned first, so surplus connections stay idle and time out
+            if let Some(idle) = state.idle.pop_back() {
+                drop(state);
+                let Idle { mut conn, created, .. } = idle;
+                if shared.options.test_on_checkout {
+                    if let Err(_error) = shared.manager.is_valid(&mut conn) {
+                        debug!(error = %_error, "discarding pooled connection that failed its health check");
+                        drop(conn);
+                        shared.release_slot();
+                        state = shared.lock();
+                        continue;
+                    }
+                }
+                return Ok(PooledConnection { pool: self.clone(), conn: Some(conn), created });
+            }
+
+            if state.total < shared.options.max_size {
+                state.total += 1;
+                drop(state);
+                return match shared.manager.connect() {
+                    Ok(conn) => Ok(PooledConnection { pool: self.clone(), conn: Some(conn), created: Instant::now() }),
+                    Err(e) => {
+                        shared.release_slot();
+                        Err(e)
+                    }
+                };
+            }
+
+            if now >= deadline {
+                return Err(InformixError::PoolTimeout(shared.options.acquire_timeout));
+            }
+            state = shared.available
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Number of idle connections and of all open connections.
+    pub fn status(&self) -> (usize, usize) {
+        let state = self.shared.lock();
+        (state.idle.len(), state.total)
+    }
+
+    pub fn options(&self) -> &PoolOptions {
+        &self.shared.options
+    }
+}
+
+/// A connection checked out of a `Pool`; it is reset and returned on drop.
+pub struct PooledConnection<M: ConnectionManager> {
+    pool: Pool<M>,
+    conn: Option<M::Connection>,
+    created: Instant,
+}
+
+impl<M: ConnectionManager> Deref for PooledConnection<M> {
+    type Target = M::Connection;
+
+    fn deref(&self) -> &M::Connection {
+        self.conn.as_ref().expect("connection is present until drop")
+    }
+}
+
+impl<M: ConnectionManager> DerefMut for PooledConnection<M> {
+    fn deref_mut(&mut self) -> &mut M::Connection {
+        self.conn.as_mut().expect("connection is present until drop")
+    }
+}
+
+impl<M: ConnectionManager> Drop for PooledConnection<M> {
+    fn drop(&mut self) {
+        let Some(mut conn) = self.conn.take() else { return };
+        let shared = &self.pool.shared;
+        if let Err(_error) = shared.manager.reset(&mut conn) {
+            debug!(error = %_error, "discarding pooled connection that could not be reset");
+            drop(conn);
+            shared.release_slot();
+            return;
+        }
+        let now = Instant::now();
+        if shared.expired(self.created, now) {
+            drop(conn);
+            shared.release_slot();
+            return;
+        }
+        let expired = {
+            let mut state = shared.lock();
+            state.idle.push_back(Idle { conn, created: self.created, returned: now });
+            shared.take_expired(&mut state, now)
+        };
+        if expired.is_empty() {
+            shared.available.notify_one();
+        } else {
+            drop(expired);
+            shared.available.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+
+    #[derive(Default)]
+    struct Driver {
+        connects: AtomicUsize,
+        validations: AtomicUsize,
+        resets: AtomicUsize,
+        fail_connect: AtomicBool,
+        fail_validation: AtomicBool,
+        fail_reset: AtomicBool,
+    }
+
+    fn failure(what: &str) -> InformixError {
+        InformixError::ConnectionError(what.to_string(), Vec::new())
+    }
+
+    /// Hands out connections numbered in the order they were opened.
+    #[derive(Clone, Default)]
+    struct MockManager(Arc<Driver>);
+
+    impl ConnectionManager for MockManager {
+        type Connection = usize;
+
+        fn connect(&self) -> Result<usize> {
+            if self.0.fail_connect.load(Ordering::SeqCst) {
+                return Err(failure("connect failed"));
+            }
+            Ok(self.0.connects.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+
+        fn is_valid(&self, _: &mut usize) -> Result<()> {
+            self.0.validations.fetch_add(1, Ordering::SeqCst);
+            if self.0.fail_validation.load(Ordering::SeqCst) {
+                return Err(failure("health check failed"));
+            }
+            Ok(())
+        }
+
+        fn reset(&self, _: &mut usize) -> Result<()> {
+            self.0.resets.fetch_add(1, Ordering::SeqCst);
+            if self.0.fail_reset.load(Ordering::SeqCst) {
+                return Err(failure("reset failed"));
+            }
+            Ok(())
+        }
+    }
+
+    fn pool(options: PoolOptions) -> (Pool<MockManager>, Arc<Driver>) {
+        let manager = MockManager::default();
+        let driver = Arc::clone(&manager.0);
+        (Pool::with_options(manager, options).unwrap(), driver)
+    }
+
+    fn options() -> PoolOptions {
+        PoolOptions {
+            max_size: 2,
+            acquire_timeout: Duration::from_millis(50),
+            idle_timeout: None,
+            max_lifetime: None,
+            ..PoolOptions::default()
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        let manager = MockManager::default();
+        assert!(Pool::with_options(manager.clone(), PoolOptions { max_size: 0, ..options() }).is_err());
+        assert!(Pool::with_options(manager, PoolOptions { min_size: 3, ..options() }).is_err());
+    }
+
+    #[test]
+    fn prefills_min_size() {
+        let (pool, driver) = pool(PoolOptions { min_size: 2, ..options() });
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.status(), (2, 2));
+    }
+
+    #[test]
+    fn times_out_at_max_size() {
+        let (pool, _) = pool(options());
+        let _first = pool.get().unwrap();
+        let _second = pool.get().unwrap();
+
+        let started = Instant::now();
+        match pool.get() {
+            Err(InformixError::PoolTimeout(timeout)) => assert_eq!(timeout, Duration::from_millis(50)),
+            other => panic!("expected PoolTimeout, got {:?}", other.map(|conn| *conn)),
+        }
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn waiter_gets_returned_connection() {
+        let (pool, driver) = pool(PoolOptions { max_size: 1, acquire_timeout: Duration::from_secs(5), ..options() });
+        let conn = pool.get().unwrap();
+        let returner = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(conn);
+        });
+        assert_eq!(*pool.get().unwrap(), 1);
+        returner.join().unwrap();
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn reuses_most_recently_returned_connection() {
+        let (pool, driver) = pool(options());
+        let first = pool.get().unwrap();
+        let second = pool.get().unwrap();
+        assert_eq!((*first, *second), (1, 2));
+        drop(first);
+        drop(second);
+        assert_eq!(*pool.get().unwrap(), 2);
+        assert_eq!(driver.resets.load(Ordering::SeqCst), 3);
+        assert_eq!(driver.validations.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn closes_connections_after_idle_timeout() {
+        let (pool, driver) = pool(PoolOptions { idle_timeout: Some(Duration::from_millis(20)), ..options() });
+        let first = pool.get().unwrap();
+        let second = pool.get().unwrap();
+        drop(first);
+        drop(second);
+        assert_eq!(pool.status(), (2, 2));
+
+        thread::sleep(Duration::from_millis(30));
+        // Both idle connections have expired: a new one is opened
+        assert_eq!(*pool.get().unwrap(), 3);
+        assert_eq!(pool.status(), (1, 1));
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn idle_timeout_keeps_min_size() {
+        let (pool, _) = pool(PoolOptions { min_size: 1, idle_timeout: Some(Duration::from_millis(10)), ..options() });
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(*pool.get().unwrap(), 1);
+        assert_eq!(pool.status(), (1, 1));
+    }
+
+    #[test]
+    fn closes_connections_at_max_lifetime() {
+        let (pool, driver) = pool(PoolOptions { max_lifetime: Some(Duration::from_millis(20)), ..options() });
+        let conn = pool.get().unwrap();
+        thread::sleep(Duration::from_millis(30));
+        drop(conn);
+        assert_eq!(pool.status(), (0, 0));
+
+        let conn = pool.get().unwrap();
+        drop(conn);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(*pool.get().unwrap(), 3);
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn discards_connection_failing_checkout_test() {
+        let (pool, driver) = pool(PoolOptions { max_size: 1, ..options() });
+        drop(pool.get().unwrap());
+        driver.fail_validation.store(true, Ordering::SeqCst);
+
+        // The slot is freed, so a new connection can be opened within max_size
+        assert_eq!(*pool.get().unwrap(), 2);
+        assert_eq!(driver.validations.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.status(), (1, 1));
+    }
+
+    #[test]
+    fn skips_checkout_test_when_disabled() {
+        let (pool, driver) = pool(PoolOptions { test_on_checkout: false, ..options() });
+        drop(pool.get().unwrap());
+        drop(pool.get().unwrap());
+        assert_eq!(driver.validations.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn discards_connection_failing_reset() {
+        let (pool, driver) = pool(PoolOptions { max_size: 1, ..options() });
+        driver.fail_reset.store(true, Ordering::SeqCst);
+        drop(pool.get().unwrap());
+        assert_eq!(pool.status(), (0, 0));
+
+        driver.fail_reset.store(false, Ordering::SeqCst);
+        assert_eq!(*pool.get().unwrap(), 2);
+    }
+
+    #[test]
+    fn connect_error_releases_slot() {
+        let (pool, driver) = pool(PoolOptions { max_size: 1, ..options() });
+        driver.fail_connect.store(true, Ordering::SeqCst);
+        assert!(matches!(pool.get(), Err(InformixError::ConnectionError(..))));
+        assert_eq!(pool.status(), (0, 0));
+
+        driver.fail_connect.store(false, Ordering::SeqCst);
+        assert_eq!(*pool.get().unwrap(), 1);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::errors::InformixError;
+use crate::pool::{ConnectionManager, InformixManager};
+use crate::Connection;
+
+/// An r2d2 `ManageConnection` for Informix, built from a connection string.
+///
+/// ```no_run
+/// use informix_rust::r2d2::InformixConnectionManager;
+///
+/// let manager = InformixConnectionManager::new("DSN=stores;UID=informix;PWD=secret");
+/// let pool = r2d2::Pool::builder().max_size(10).build(manager).unwrap();
+/// let conn = pool.get().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InformixConnectionManager {
+    inner: InformixManager,
+}
+
+impl InformixConnectionManager {
+    pub fn new(conn_string: &str) -> Self {
+        InformixConnectionManager { inner: InformixManager::new(conn_string) }
+    }
+
+    /// Replaces the `SELECT 1 FROM sysmaster:sysdual` ping run by `is_valid`.
+    pub fn health_query(self, query: &str) -> Self {
+        InformixConnectionManager { inner: self.inner.health_query(query) }
+    }
+}
+
+impl ::r2d2::ManageConnection for InformixConnectionManager {
+    type Connection = Connection;
+    type Error = InformixError;
+
+    fn connect(&self) -> Result<Connection, InformixError> {
+        self.inner.connect()
+    }
+
+    fn is_valid(&self, conn: &mut Connection) -> Result<(), InformixError> {
+        self.inner.is_valid(conn)
+    }
+
+    /// A connection whose state cannot be read is treated as broken.
+    fn has_broken(&self, conn: &mut Connection) -> bool {
+        conn.is_dead().unwrap_or(true)
+    }
+}
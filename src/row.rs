@@ -0,0 +1,52 @@
+use indexmap::IndexMap;
+use crate::errors::{InformixError, Result};
+use crate::types::FromSql;
+use crate::value::Value;
+use crate::Statement;
+
+/// The current row of a `Statement`, as returned by `Statement::fetch_row`.
+///
+/// Columns are indexed from 0 and read on demand with `SQLGetData`.
+pub struct Row<'a> {
+    stmt: &'a Statement<'a>,
+}
+
+impl<'a> Row<'a> {
+    pub(crate) fn new(stmt: &'a Statement<'a>) -> Self {
+        Row { stmt }
+    }
+
+    /// Reads column `idx` as `T`, requesting `T::C_TYPE` from the driver.
+    pub fn get<T: FromSql>(&self, idx: usize) -> Result<T> {
+        let column = u16::try_from(idx + 1)
+            .map_err(|_| InformixError::DataFetchError(format!("Column index out of range: {}", idx), Vec::new()))?;
+        match self.stmt.get_data(column, T::C_TYPE)? {
+            Some(raw) => T::from_sql(&raw),
+            None => T::from_null().ok_or_else(|| InformixError::DataFetchError(
+                format!("Column {} is NULL; read it as Option<T>", idx), Vec::new()
+            )),
+        }
+    }
+
+    /// Reads the column called `name` as `T`; see `Statement::column_index` for matching rules.
+    pub fn get_by_name<T: FromSql>(&self, name: &str) -> Result<T> {
+        self.get(self.stmt.column_index(name)?)
+    }
+
+    /// Reads column `idx` as a `Value` chosen from the described column type.
+    pub fn get_value(&self, idx: usize) -> Result<Value> {
+        let column = self.stmt.columns()?.get(idx)
+            .ok_or_else(|| InformixError::DataFetchError(format!("Column index out of range: {}", idx), Vec::new()))?;
+        Value::read(self.stmt, (idx + 1) as u16, column)
+    }
+
+    /// Reads every column as a `Value`, keyed by column name in result set order.
+    pub fn into_map(self) -> Result<IndexMap<String, Value>> {
+        let columns = self.stmt.columns()?;
+        let mut map = IndexMap::with_capacity(columns.len());
+        for (idx, column) in columns.iter().enumerate() {
+            map.insert(column.name.clone(), Value::read(self.stmt, (idx + 1) as u16, column)?);
+        }
+        Ok(map)
+    }
+}
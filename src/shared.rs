@@ -0,0 +1,36 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use crate::Connection;
+
+/// A `Connection` that can be cloned and shared between threads.
+///
+/// `Connection` is `Send` but not `Sync`; this wrapper serializes access with
+/// a mutex so that one thread uses the connection at a time.
+#[derive(Clone)]
+pub struct SharedConnection {
+    inner: Arc<Mutex<Connection>>,
+}
+
+impl SharedConnection {
+    pub fn new(conn: Connection) -> Self {
+        SharedConnection { inner: Arc::new(Mutex::new(conn)) }
+    }
+
+    /// Locks the connection for the calling thread until the guard is dropped.
+    ///
+    /// A panic while the lock was held does not make the connection unusable,
+    /// so a poisoned lock is recovered rather than reported.
+    pub fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` with exclusive access to the connection.
+    pub fn with<R>(&self, f: impl FnOnce(&Connection) -> R) -> R {
+        f(&self.lock())
+    }
+}
+
+impl From<Connection> for SharedConnection {
+    fn from(conn: Connection) -> Self {
+        SharedConnection::new(conn)
+    }
+}
@@ -0,0 +1,147 @@
+use std::ops::Deref;
+use crate::errors::{InformixError, Result};
+use crate::Connection;
+
+/// A transaction on a `Connection`, started with `Connection::transaction`.
+///
+/// Dropping the guard without calling `commit` rolls the transaction back.
+/// Either way the connection's previous autocommit mode is restored.
+pub struct Transaction<'a> {
+    conn: &'a Connection,
+    restore_autocommit: bool,
+    finished: bool,
+}
+
+impl<'a> Transaction<'a> {
+    /// Takes the connection exclusively, so no other guard and no direct
+    /// `Connection::commit` or `rollback` can interleave with this one.
+    pub(crate) fn begin(conn: &'a mut Connection) -> Result<Self> {
+        let conn = &*conn;
+        let restore_autocommit = begin(conn)?;
+        Ok(Transaction { conn, restore_autocommit, finished: false })
+    }
+
+    pub fn commit(mut self) -> Result<()> {
+        self.finish(Connection::commit)
+    }
+
+    /// Sets a savepoint inside this transaction.
+    ///
+    /// Dropping the returned guard without calling `Savepoint::release` rolls
+    /// the transaction back to the savepoint, leaving earlier work intact.
+    pub fn savepoint(&mut self, name: &str) -> Result<Savepoint<'_>> {
+        Savepoint::set(self.conn, name)
+    }
+
+    pub fn rollback(mut self) -> Result<()> {
+        self.finish(Connection::rollback)
+    }
+
+    fn finish(&mut self, end: fn(&Connection) -> Result<()>) -> Result<()> {
+        self.finished = true;
+        finish(self.conn, self.restore_autocommit, end)
+    }
+}
+
+/// Turns autocommit off, returning whether it was on and must be restored.
+pub(crate) fn begin(conn: &Connection) -> Result<bool> {
+    let restore_autocommit = conn.autocommit()?;
+    if restore_autocommit {
+        conn.set_autocommit(false)?;
+    }
+    Ok(restore_autocommit)
+}
+
+/// Ends the transaction with `end`, then restores autocommit if `begin` turned it off.
+pub(crate) fn finish(conn: &Connection, restore_autocommit: bool, end: fn(&Connection) -> Result<()>) -> Result<()> {
+    let result = end(conn);
+    if restore_autocommit {
+        // Restore even if ending the transaction failed, but report the first error
+        let restored = conn.set_autocommit(true);
+        result?;
+        restored
+    } else {
+        result
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.finish(Connection::rollback);
+        }
+    }
+}
+
+/// A savepoint inside a `Transaction`, set with `Transaction::savepoint`.
+pub struct Savepoint<'a> {
+    conn: &'a Connection,
+    name: String,
+    finished: bool,
+}
+
+impl<'a> Savepoint<'a> {
+    fn set(conn: &'a Connection, name: &str) -> Result<Self> {
+        let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            && name.len() <= 128;
+        if !valid {
+            return Err(InformixError::InvalidInput(format!("Invalid savepoint name: {:?}", name)));
+        }
+        conn.execute(&format!("SAVEPOINT {}", name))?;
+        Ok(Savepoint { conn, name: name.to_string(), finished: false })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets a savepoint nested inside this one.
+    pub fn savepoint(&mut self, name: &str) -> Result<Savepoint<'_>> {
+        Savepoint::set(self.conn, name)
+    }
+
+    /// Keeps the work done since the savepoint and discards the savepoint.
+    pub fn release(mut self) -> Result<()> {
+        self.finished = true;
+        self.conn.execute(&format!("RELEASE SAVEPOINT {}", self.name))?;
+        Ok(())
+    }
+
+    /// Undoes the work done since the savepoint and discards the savepoint.
+    pub fn rollback(mut self) -> Result<()> {
+        self.rollback_to()
+    }
+
+    fn rollback_to(&mut self) -> Result<()> {
+        self.finished = true;
+        self.conn.execute(&format!("ROLLBACK TO SAVEPOINT {}", self.name))?;
+        // Informix keeps a savepoint after rolling back to it
+        self.conn.execute(&format!("RELEASE SAVEPOINT {}", self.name))?;
+        Ok(())
+    }
+}
+
+impl Deref for Savepoint<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.rollback_to();
+        }
+    }
+}
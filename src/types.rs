@@ -0,0 +1,232 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::os::raw::c_short;
+use crate::errors::{InformixError, Result};
+use crate::{SQL_C_BINARY, SQL_C_BIT, SQL_C_CHAR, SQL_C_DOUBLE, SQL_C_FLOAT, SQL_C_LONG,
+    SQL_C_SBIGINT, SQL_C_SHORT, SQL_C_TYPE_DATE, SQL_C_TYPE_TIME, SQL_C_TYPE_TIMESTAMP};
+
+/// Conversion of a fetched column value into a Rust type.
+///
+/// This is the counterpart of `ToSql`: `C_TYPE` is the C data type passed to
+/// `SQLGetData`, and `from_sql` decodes the bytes the driver wrote for it.
+/// SQL NULL is handed to `from_null`, which only succeeds for `Option<T>`.
+pub trait FromSql: Sized {
+    const C_TYPE: c_short;
+
+    fn from_sql(raw: &[u8]) -> Result<Self>;
+
+    fn from_null() -> Option<Self> {
+        None
+    }
+}
+
+impl<T: FromSql> FromSql for Option<T> {
+    const C_TYPE: c_short = T::C_TYPE;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        T::from_sql(raw).map(Some)
+    }
+
+    fn from_null() -> Option<Self> {
+        Some(None)
+    }
+}
+
+fn fixed<const N: usize>(raw: &[u8], type_name: &str) -> Result<[u8; N]> {
+    raw.get(..N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| InformixError::DataFetchError(
+            format!("Expected {} bytes for {}, got {}", N, type_name, raw.len()), Vec::new()
+        ))
+}
+
+impl FromSql for i16 {
+    const C_TYPE: c_short = SQL_C_SHORT;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(i16::from_ne_bytes(fixed(raw, "i16")?))
+    }
+}
+
+impl FromSql for i32 {
+    const C_TYPE: c_short = SQL_C_LONG;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(i32::from_ne_bytes(fixed(raw, "i32")?))
+    }
+}
+
+impl FromSql for i64 {
+    const C_TYPE: c_short = SQL_C_SBIGINT;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(i64::from_ne_bytes(fixed(raw, "i64")?))
+    }
+}
+
+impl FromSql for f32 {
+    const C_TYPE: c_short = SQL_C_FLOAT;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(f32::from_ne_bytes(fixed(raw, "f32")?))
+    }
+}
+
+impl FromSql for f64 {
+    const C_TYPE: c_short = SQL_C_DOUBLE;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(f64::from_ne_bytes(fixed(raw, "f64")?))
+    }
+}
+
+impl FromSql for bool {
+    const C_TYPE: c_short = SQL_C_BIT;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        let [bit] = fixed(raw, "bool")?;
+        Ok(bit != 0)
+    }
+}
+
+impl FromSql for String {
+    const C_TYPE: c_short = SQL_C_CHAR;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(String::from_utf8_lossy(raw).into_owned())
+    }
+}
+
+impl FromSql for Vec<u8> {
+    const C_TYPE: c_short = SQL_C_BINARY;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        Ok(raw.to_vec())
+    }
+}
+
+impl FromSql for NaiveDate {
+    const C_TYPE: c_short = SQL_C_TYPE_DATE;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        // SQL_DATE_STRUCT: year (i16), month (u16), day (u16)
+        let bytes: [u8; 6] = fixed(raw, "NaiveDate")?;
+        let year = i16::from_ne_bytes([bytes[0], bytes[1]]);
+        let month = u16::from_ne_bytes([bytes[2], bytes[3]]);
+        let day = u16::from_ne_bytes([bytes[4], bytes[5]]);
+        NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+            .ok_or_else(|| InformixError::DataFetchError(
+                format!("Invalid date: {}-{}-{}", year, month, day), Vec::new()
+            ))
+    }
+}
+
+impl FromSql for NaiveTime {
+    const C_TYPE: c_short = SQL_C_TYPE_TIME;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        // SQL_TIME_STRUCT: hour, minute, second (u16 each)
+        let bytes: [u8; 6] = fixed(raw, "NaiveTime")?;
+        let hour = u16::from_ne_bytes([bytes[0], bytes[1]]);
+        let minute = u16::from_ne_bytes([bytes[2], bytes[3]]);
+        let second = u16::from_ne_bytes([bytes[4], bytes[5]]);
+        NaiveTime::from_hms_opt(hour.into(), minute.into(), second.into())
+            .ok_or_else(|| InformixError::DataFetchError(
+                format!("Invalid time: {}:{}:{}", hour, minute, second), Vec::new()
+            ))
+    }
+}
+
+impl FromSql for NaiveDateTime {
+    const C_TYPE: c_short = SQL_C_TYPE_TIMESTAMP;
+
+    fn from_sql(raw: &[u8]) -> Result<Self> {
+        // SQL_TIMESTAMP_STRUCT: SQL_DATE_STRUCT, SQL_TIME_STRUCT, fraction (u32 nanoseconds)
+        let bytes: [u8; 16] = fixed(raw, "NaiveDateTime")?;
+        let date = NaiveDate::from_sql(&bytes[..6])?;
+        let time = NaiveTime::from_sql(&bytes[6..12])?;
+        let fraction = u32::from_ne_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+        let time = time.with_nanosecond(fraction)
+            .ok_or_else(|| InformixError::DataFetchError(format!("Invalid fraction: {}", fraction), Vec::new()))?;
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToSql;
+
+    fn date_bytes(year: i16, month: u16, day: u16) -> Vec<u8> {
+        [year.to_ne_bytes(), month.to_ne_bytes(), day.to_ne_bytes()].concat()
+    }
+
+    fn time_bytes(hour: u16, minute: u16, second: u16) -> Vec<u8> {
+        [hour.to_ne_bytes(), minute.to_ne_bytes(), second.to_ne_bytes()].concat()
+    }
+
+    /// The bytes a `ToSql` value hands to the driver.
+    fn bound_bytes<T: ToSql>(value: &T) -> Vec<u8> {
+        let param = value.to_sql().unwrap();
+        let len = param.buffer_length() as usize;
+        unsafe { std::slice::from_raw_parts(param.value_ptr() as *const u8, len) }.to_vec()
+    }
+
+    #[test]
+    fn date_struct_layout() {
+        let date = NaiveDate::from_sql(&date_bytes(2024, 2, 29)).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert!(matches!(NaiveDate::from_sql(&date_bytes(2023, 2, 29)), Err(InformixError::DataFetchError(..))));
+    }
+
+    #[test]
+    fn time_struct_layout() {
+        assert_eq!(NaiveTime::from_sql(&time_bytes(23, 59, 58)).unwrap(), NaiveTime::from_hms_opt(23, 59, 58).unwrap());
+        assert!(NaiveTime::from_sql(&time_bytes(24, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn timestamp_struct_layout() {
+        let raw = [date_bytes(1999, 12, 31), time_bytes(8, 30, 15), 120_000_000u32.to_ne_bytes().to_vec()].concat();
+        let expected = NaiveDate::from_ymd_opt(1999, 12, 31).unwrap().and_hms_nano_opt(8, 30, 15, 120_000_000).unwrap();
+        assert_eq!(NaiveDateTime::from_sql(&raw).unwrap(), expected);
+    }
+
+    #[test]
+    fn short_buffer_is_an_error() {
+        match i32::from_sql(&[1, 2]) {
+            Err(InformixError::DataFetchError(message, _)) => assert_eq!(message, "Expected 4 bytes for i32, got 2"),
+            other => panic!("expected DataFetchError, got {:?}", other),
+        }
+        assert!(NaiveDateTime::from_sql(&date_bytes(1999, 12, 31)).is_err());
+    }
+
+    #[test]
+    fn scalars_decode_native_endian() {
+        assert_eq!(i16::from_sql(&(-7i16).to_ne_bytes()).unwrap(), -7);
+        assert_eq!(i64::from_sql(&i64::MIN.to_ne_bytes()).unwrap(), i64::MIN);
+        assert_eq!(f64::from_sql(&1.5f64.to_ne_bytes()).unwrap(), 1.5);
+        assert!(bool::from_sql(&[1]).unwrap());
+        assert_eq!(String::from_sql(b"abc").unwrap(), "abc");
+    }
+
+    #[test]
+    fn only_option_accepts_null() {
+        assert_eq!(<Option<i32>>::from_null(), Some(None));
+        assert_eq!(i32::from_null(), None);
+        assert_eq!(<Option<i32>>::from_sql(&5i32.to_ne_bytes()).unwrap(), Some(5));
+    }
+
+    #[test]
+    fn bound_structs_decode_to_the_same_value() {
+        let date = NaiveDate::from_ymd_opt(2001, 9, 9).unwrap();
+        assert_eq!(NaiveDate::from_sql(&bound_bytes(&date)).unwrap(), date);
+
+        let time = NaiveTime::from_hms_opt(1, 46, 40).unwrap();
+        assert_eq!(NaiveTime::from_sql(&bound_bytes(&time)).unwrap(), time);
+
+        // Informix keeps FRACTION(5), so the bound value is truncated to 10 microseconds
+        let timestamp = date.and_hms_nano_opt(1, 46, 40, 123_456_789).unwrap();
+        let bound = NaiveDateTime::from_sql(&bound_bytes(&timestamp)).unwrap();
+        assert_eq!(bound, date.and_hms_nano_opt(1, 46, 40, 123_450_000).unwrap());
+    }
+}
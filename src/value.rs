@@ -0,0 +1,102 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use crate::column::ColumnDesc;
+use crate::errors::Result;
+use crate::types::FromSql;
+use crate::params::SqlParam;
+use crate::{Statement, ToSql};
+use crate::{SQL_BIGINT, SQL_BINARY, SQL_BIT, SQL_CHAR, SQL_DECIMAL, SQL_DOUBLE, SQL_FLOAT,
+    SQL_INFX_BIGINT, SQL_INFX_UDT_LVARCHAR, SQL_INTEGER, SQL_INTERVAL_MINUTE_TO_SECOND,
+    SQL_INTERVAL_YEAR, SQL_LONGVARBINARY, SQL_LONGVARCHAR, SQL_NUMERIC, SQL_REAL, SQL_SMALLINT,
+    SQL_TYPE_DATE, SQL_TYPE_TIME, SQL_TYPE_TIMESTAMP, SQL_VARBINARY, SQL_VARCHAR, SQL_WCHAR,
+    SQL_WLONGVARCHAR, SQL_WVARCHAR};
+
+/// A dynamically typed Informix value.
+///
+/// DECIMAL, MONEY and INTERVAL are kept in the textual form returned by the
+/// driver so no precision is lost.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    SmallInt(i16),
+    Integer(i32),
+    /// INT8 and BIGINT
+    BigInt(i64),
+    Serial(i32),
+    /// SERIAL8 and BIGSERIAL
+    BigSerial(i64),
+    SmallFloat(f32),
+    Float(f64),
+    Decimal(String),
+    Money(String),
+    /// CHAR, VARCHAR, LVARCHAR, NCHAR and NVARCHAR
+    Char(String),
+    Date(NaiveDate),
+    /// DATETIME with a YEAR TO ... qualifier
+    DateTime(NaiveDateTime),
+    /// DATETIME HOUR TO SECOND
+    Time(NaiveTime),
+    Interval(String),
+    Boolean(bool),
+    Byte(Vec<u8>),
+    Text(String),
+}
+
+fn read<T: FromSql>(stmt: &Statement<'_>, column: u16) -> Result<Option<T>> {
+    stmt.get_data(column, T::C_TYPE)?
+        .map(|raw| T::from_sql(&raw))
+        .transpose()
+}
+
+impl Value {
+    /// Reads column `column` (1-based) of the current row of `stmt`, using `desc` to pick the variant.
+    pub(crate) fn read(stmt: &Statement<'_>, column: u16, desc: &ColumnDesc) -> Result<Value> {
+        let type_name = desc.type_name.to_ascii_lowercase();
+        let is_serial = type_name.contains("serial");
+        let value = match desc.sql_type {
+            SQL_SMALLINT => read(stmt, column)?.map(Value::SmallInt),
+            SQL_INTEGER if is_serial => read(stmt, column)?.map(Value::Serial),
+            SQL_INTEGER => read(stmt, column)?.map(Value::Integer),
+            SQL_BIGINT | SQL_INFX_BIGINT if is_serial => read(stmt, column)?.map(Value::BigSerial),
+            SQL_BIGINT | SQL_INFX_BIGINT => read(stmt, column)?.map(Value::BigInt),
+            SQL_REAL => read(stmt, column)?.map(Value::SmallFloat),
+            SQL_FLOAT | SQL_DOUBLE => read(stmt, column)?.map(Value::Float),
+            SQL_DECIMAL | SQL_NUMERIC if type_name == "money" => read(stmt, column)?.map(Value::Money),
+            SQL_DECIMAL | SQL_NUMERIC => read(stmt, column)?.map(Value::Decimal),
+            SQL_TYPE_DATE => read(stmt, column)?.map(Value::Date),
+            SQL_TYPE_TIMESTAMP => read(stmt, column)?.map(Value::DateTime),
+            SQL_TYPE_TIME => read(stmt, column)?.map(Value::Time),
+            SQL_INTERVAL_YEAR..=SQL_INTERVAL_MINUTE_TO_SECOND => read(stmt, column)?.map(Value::Interval),
+            SQL_BIT => read(stmt, column)?.map(Value::Boolean),
+            SQL_BINARY | SQL_VARBINARY | SQL_LONGVARBINARY => read(stmt, column)?.map(Value::Byte),
+            SQL_LONGVARCHAR | SQL_WLONGVARCHAR if type_name == "text" => read(stmt, column)?.map(Value::Text),
+            SQL_CHAR | SQL_VARCHAR | SQL_LONGVARCHAR | SQL_WCHAR | SQL_WVARCHAR | SQL_WLONGVARCHAR
+            | SQL_INFX_UDT_LVARCHAR => read(stmt, column)?.map(Value::Char),
+            // Anything else is returned in the driver's character representation
+            _ => read(stmt, column)?.map(Value::Char),
+        };
+        Ok(value.unwrap_or(Value::Null))
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+impl ToSql for Value {
+    fn to_sql(&self) -> Result<SqlParam> {
+        match self {
+            Value::Null => Ok(SqlParam::null()),
+            Value::SmallInt(v) => v.to_sql(),
+            Value::Integer(v) | Value::Serial(v) => v.to_sql(),
+            Value::BigInt(v) | Value::BigSerial(v) => v.to_sql(),
+            Value::SmallFloat(v) => v.to_sql(),
+            Value::Float(v) => v.to_sql(),
+            Value::Decimal(v) | Value::Money(v) | Value::Char(v) | Value::Interval(v) | Value::Text(v) => v.to_sql(),
+            Value::Date(v) => v.to_sql(),
+            Value::DateTime(v) => v.to_sql(),
+            Value::Time(v) => v.to_sql(),
+            Value::Boolean(v) => v.to_sql(),
+            Value::Byte(v) => v.to_sql(),
+        }
+    }
+}
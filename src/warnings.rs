@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::os::raw::{c_short, c_void};
+use std::sync::Arc;
+use crate::errors::Diagnostic;
+use crate::get_diagnostics;
+
+/// Callback invoked for every warning a connection or its statements receive.
+pub type WarningHook = Arc<dyn Fn(&Diagnostic) + Send + Sync>;
+
+/// A `WarningHook` that logs each warning.
+///
+/// Warnings are emitted as `tracing` events when the `tracing` feature is
+/// enabled and written to stderr otherwise.
+pub fn log_warning(diagnostic: &Diagnostic) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: "informix_rust",
+        sqlstate = %diagnostic.sqlstate,
+        native_code = diagnostic.native_code,
+        message = %diagnostic.message,
+        "warning"
+    );
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("Informix warning: {}", diagnostic);
+}
+
+/// Diagnostic records from calls that returned `SQL_SUCCESS_WITH_INFO`.
+#[derive(Default)]
+pub(crate) struct Warnings {
+    records: RefCell<Vec<Diagnostic>>,
+    hook: Option<WarningHook>,
+}
+
+impl Warnings {
+    pub(crate) fn new(hook: Option<WarningHook>) -> Self {
+        Warnings { records: RefCell::new(Vec::new()), hook }
+    }
+
+    pub(crate) fn hook(&self) -> Option<WarningHook> {
+        self.hook.clone()
+    }
+
+    pub(crate) fn set_hook(&mut self, hook: WarningHook) {
+        self.hook = Some(hook);
+    }
+
+    /// Collects the info records attached to `handle` by the last call.
+    pub(crate) fn record(&self, handle_type: c_short, handle: *mut c_void) {
+        self.extend(get_diagnostics(handle_type, handle));
+    }
+
+    pub(crate) fn extend(&self, diagnostics: Vec<Diagnostic>) {
+        if let Some(hook) = &self.hook {
+            diagnostics.iter().for_each(|diagnostic| hook(diagnostic));
+        }
+        self.records.borrow_mut().extend(diagnostics);
+    }
+
+    pub(crate) fn get(&self) -> Vec<Diagnostic> {
+        self.records.borrow().clone()
+    }
+
+    pub(crate) fn clear(&self) {
+        self.records.borrow_mut().clear();
+    }
+}